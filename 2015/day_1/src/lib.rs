@@ -0,0 +1,134 @@
+use std::fmt;
+
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+fn decode(c: char) -> isize {
+    match c {
+        '(' => 1,
+        ')' => -1,
+        _ => 0
+    }
+}
+
+fn find_floor(input: &str) -> isize {
+    input
+        .chars()
+        .fold(0, |acc, x| acc + decode(x))
+}
+
+fn enters_basement(input: &str) -> Option<usize> {
+    let mut sum = 0;
+    for (i, c) in input.chars().enumerate() {
+        sum += decode(c);
+        if sum == -1 {
+            return Some(i)
+        }
+    }
+    None
+}
+
+// the position of the instruction that first takes Santa into the basement,
+// counting from 1, if any does
+#[derive(Debug, PartialEq)]
+pub struct Basement(Option<usize>);
+
+impl fmt::Display for Basement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(position) => write!(f, "{}", position),
+            None => write!(f, "never"),
+        }
+    }
+}
+
+pub struct Day1 {
+    instructions: String,
+}
+
+impl Solution for Day1 {
+    type Part1 = isize;
+    type Part2 = Basement;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let instructions = input.trim();
+        if let Some((i, c)) = instructions.char_indices().find(|(_, c)| !"()".contains(*c)) {
+            let token = &instructions[i..i + c.len_utf8()];
+            return Err(ParseError::in_line("expected ( or ), found", instructions, token).at(1, instructions));
+        }
+        Ok(Self { instructions: instructions.into() })
+    }
+
+    fn part1(&self) -> isize {
+        find_floor(&self.instructions)
+    }
+
+    fn part2(&self) -> Basement {
+        Basement(enters_basement(&self.instructions).map(|i| i + 1)) // not 0-indexed
+    }
+}
+
+// `size` instructions wandering up and down, plus however many it takes to
+// reach the basement if they never do
+impl Generator for Day1 {
+    const SIZE: usize = 7000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut floor = 0;
+        let mut lowest = 0;
+        let mut instructions = String::with_capacity(size + 1);
+        for _ in 0..size {
+            let up = rng.chance(0.5);
+            floor += if up { 1 } else { -1 };
+            lowest = lowest.min(floor);
+            instructions.push(if up { '(' } else { ')' });
+        }
+        if lowest > -1 {
+            instructions.extend(std::iter::repeat_n(')', floor as usize + 1));
+        }
+        instructions + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day1>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day1>();
+    }
+
+    #[test]
+    fn test() {
+        assert_eq!(find_floor("(())"), 0);
+        assert_eq!(find_floor("()()"), 0);
+    }
+
+    #[test]
+    fn enters_basement_counts_from_one() {
+        assert_eq!(Day1::parse("()())").unwrap().part2(), Basement(Some(5)));
+    }
+
+    // found by fuzzing: Santa need never go below the ground floor
+    #[test]
+    fn part2_allows_never_entering_the_basement() {
+        let basement = Day1::parse("(()\n").unwrap().part2();
+        assert_eq!(basement, Basement(None));
+        assert_eq!(basement.to_string(), "never");
+    }
+
+    #[test]
+    fn parse_rejects_other_characters() {
+        let err = Day1::parse("(()x)").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 4, "x"));
+    }
+}
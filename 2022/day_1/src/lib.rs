@@ -0,0 +1,99 @@
+use aoc_common::parse::parse_groups;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{number, parse_lines, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+pub struct Day1 {
+    // the 3 largest totals, largest first
+    top_three: Vec<u64>,
+}
+
+fn top_three(totals: impl Iterator<Item = u64>) -> Vec<u64> {
+    let mut top_four: Vec<u64> = vec![0; 4]; // 4-element zeroed-out vector
+    for sum in totals {
+        let smallest = top_four[0];
+        if sum > smallest {
+            top_four[0] = sum;
+            top_four.sort_unstable();
+        }
+    }
+
+    top_four.reverse();
+    top_four.pop();
+    top_four
+}
+
+impl Solution for Day1 {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        // each elf's calories are a group of lines, totalled in 64 bits so
+        // that no number of snacks can overflow them
+        let totals = parse_groups(input, |elf| Ok(parse_lines(elf, number::<u32>)?.into_iter().map(u64::from).sum()))?;
+        Ok(Self { top_three: top_three(totals.into_iter()) })
+    }
+
+    // the most calories carried by any one elf
+    fn part1(&self) -> u64 {
+        self.top_three[0]
+    }
+
+    // the sum of the calories carried by the top three elves
+    fn part2(&self) -> u64 {
+        self.top_three.iter().sum()
+    }
+}
+
+// `size` elves, each carrying a handful of snacks
+impl Generator for Day1 {
+    const SIZE: usize = 250;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let elves: Vec<String> = (0..size)
+            .map(|_| (0..rng.between(1, 15)).map(|_| format!("{}\n", rng.between(1000, 60_000))).collect())
+            .collect();
+        elves.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day1>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day1>();
+    }
+
+    #[test]
+    fn top_three_returns_largest_first() {
+        assert_eq!(top_three([3, 9, 1, 7, 5].into_iter()), vec![9, 7, 5]);
+    }
+
+    // found by fuzzing: two elves' worth of calories overflowed 32 bits
+    #[test]
+    fn totals_can_pass_32_bits() {
+        let day = Day1::parse("4184090412
+412
+
+4294967295
+4294967295
+").unwrap();
+        assert_eq!(day.part1(), 8_589_934_590);
+        assert_eq!(day.part2(), 12_774_025_414);
+    }
+
+    #[test]
+    fn parse_reports_line_of_bad_calories() {
+        let err = Day1::parse("1000\n2000\n\n3k00\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (4, 1, "3k00"));
+    }
+}
@@ -0,0 +1,277 @@
+use aoc_common::animation::{Frame, Frames, Simulation};
+use aoc_common::image::{self, Image, Picture, Rgb};
+use aoc_common::parse::tokens;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{number, parse_lines, Grid, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+enum Command {
+    NoOp,
+    AddX(isize),
+}
+
+#[derive(Debug)]
+// Sprite: 3 pixels wide.  X is the position of the middle pixel
+// A single pixel is drawn each cycle
+struct Register {
+    x: isize,
+    cycles: usize,
+    sum: isize,
+    stream: Stream,
+    // a frame per cycle, when recording
+    frames: Option<Vec<Frame>>,
+}
+
+const DARK_PIXEL: char = '.';
+const LIT_PIXEL: char = '#';
+const SCREEN_X: usize = 40;
+const SCREEN_Y: usize = 6;
+
+type Stream = Vec<char>;
+type Screen = Grid<char>;
+
+impl Register {
+    fn new() -> Self {
+        Self { x: 1, cycles: 0, sum: 0, stream: Vec::with_capacity(SCREEN_X * SCREEN_Y), frames: None }
+    }
+
+    fn recording() -> Self {
+        Self { frames: Some(vec![]), ..Self::new() }
+    }
+
+    fn noop(&mut self) {
+        self.tick();
+    }
+
+    fn addx(&mut self, x: isize) {
+        self.tick();
+        self.tick();
+        self.x += x;
+    }
+
+    fn tick(&mut self) {
+        self.draw_pixel();
+        self.cycles += 1;
+        if self.is_signal() {
+            self.sum += self.signal_strength();
+        }
+        if let Some(mut frames) = self.frames.take() {
+            frames.push(self.frame());
+            self.frames = Some(frames);
+        }
+    }
+
+    // the screen as drawn so far
+    fn frame(&self) -> Frame {
+        let screen = Register::make_screen(&self.stream);
+        let caption = format!("cycle {}: X = {}, signal strength sum {}", self.cycles, self.x, self.sum);
+        let beam = (self.cycles - 1).min(SCREEN_X * SCREEN_Y - 1);
+        Frame::new(screen, caption).focus(beam % SCREEN_X, beam / SCREEN_X)
+    }
+
+    fn draw_pixel(&mut self) {
+        let pixel_x = (self.cycles % SCREEN_X) as isize;
+        let mut pixel = DARK_PIXEL;
+        if self.x == pixel_x - 1 || self.x == pixel_x || self.x == pixel_x + 1 {
+            pixel = LIT_PIXEL;
+        }
+        self.stream.push(pixel);
+    }
+
+    fn is_signal(&self) -> bool {
+        matches!(self.cycles, 20 | 60 | 100 | 140 | 180 | 220)
+    }
+
+    fn signal_strength(&self) -> isize {
+         self.x * (self.cycles as isize)
+    }
+
+    fn parse(line: &str) -> Result<Command, ParseError> {
+        match tokens(line)[..] {
+            ["noop"] => Ok(Command::NoOp),
+            // 32-bit values keep X, and the signal strengths, far from overflowing
+            ["addx", x] => Ok(Command::AddX(number::<i32>(x).map_err(|err| err.within(line, x))? as isize)),
+            ["addx"] => Err(ParseError::in_line("expected a value after", line, line)),
+            [instruction, ..] => Err(ParseError::in_line("unknown instruction", line, instruction)),
+            [] => Err(ParseError::in_line("expected an instruction, found", line, line)),
+        }
+    }
+
+    // pixels the program didn't run long enough to draw are left blank
+    fn make_screen(stream: &Stream) -> Screen {
+        Grid::from_fn(SCREEN_X, SCREEN_Y, |x, y| *stream.get(y * SCREEN_X + x).unwrap_or(&' '))
+    }
+}
+
+pub struct Day10 {
+    commands: Vec<Command>,
+}
+
+impl Day10 {
+    fn run(&self) -> Register {
+        self.run_on(Register::new())
+    }
+
+    fn run_on(&self, mut register: Register) -> Register {
+        for command in self.commands.iter() {
+            match command {
+                Command::NoOp => register.noop(),
+                Command::AddX(x) => register.addx(*x),
+            }
+        }
+        register
+    }
+}
+
+impl Solution for Day10 {
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { commands: parse_lines(input, Register::parse)? })
+    }
+
+    // the sum of the signal strengths
+    fn part1(&self) -> isize {
+        self.run().sum
+    }
+
+    // the screen, one row per line
+    fn part2(&self) -> String {
+        Register::make_screen(&self.run().stream).to_string()
+    }
+}
+
+// the screen, whichever the part, with a pixel per 8x8 square
+impl Picture for Day10 {
+    fn picture(&self, _part: u8) -> Image {
+        let screen = Register::make_screen(&self.run().stream);
+        image::scale(&image::bitmap(&screen.map(|&pixel| pixel == LIT_PIXEL), Rgb(255, 210, 80), Rgb(20, 20, 30)), 8)
+    }
+}
+
+// a frame per cycle
+impl Simulation for Day10 {
+    fn frames(self, _part: u8) -> Frames {
+        let frames = self.run_on(Register::recording()).frames.unwrap_or_default();
+        Box::new(frames.into_iter())
+    }
+}
+
+// a program running for `size` cycles, rounded up to whole rows of the
+// screen, that keeps the sprite on or next to the screen
+impl Generator for Day10 {
+    const SIZE: usize = SCREEN_X * SCREEN_Y;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let cycles = size.max(1).div_ceil(SCREEN_X) * SCREEN_X;
+        let mut program = String::new();
+        let (mut cycle, mut x) = (0, 1);
+        while cycle < cycles {
+            // an addx can't start on the last cycle, as it takes two
+            if cycle + 1 == cycles || rng.chance(0.3) {
+                program += "noop\n";
+                cycle += 1;
+            } else {
+                let target = rng.between((x - 15).max(-1), (x + 15).min(SCREEN_X as i64));
+                program += &format!("addx {}\n", target - x);
+                x = target;
+                cycle += 2;
+            }
+        }
+        program
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day10>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day10>();
+    }
+
+    // found by fuzzing
+    #[test]
+    fn parse_rejects_values_past_32_bits() {
+        let err = Day10::parse("addx 2147483648\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 6, "2147483648"));
+        let day = Day10::parse(&"addx 2147483647\n".repeat(120)).unwrap();
+        // X is 1 plus the value for each addx finished before the cycle
+        let x = |added: isize| 1 + added * 2_147_483_647;
+        assert_eq!(day.part1(), x(9) * 20 + x(29) * 60 + x(49) * 100 + x(69) * 140 + x(89) * 180 + x(109) * 220);
+    }
+
+    #[test]
+    fn short_programs_leave_the_rest_of_the_screen_blank() {
+        let day = Day10::parse("noop\n").unwrap();
+        let screen = day.part2();
+        assert_eq!(screen.lines().count(), SCREEN_Y);
+        assert_eq!(screen.lines().next().unwrap(), format!("#{}", " ".repeat(SCREEN_X - 1)));
+        assert!(screen.lines().skip(1).all(|row| row.trim().is_empty()));
+        assert_eq!(day.picture(2).width(), SCREEN_X * 8);
+    }
+
+    // the larger program from the README's worked example
+    fn example() -> Day10 {
+        let examples = aoc_common::examples::examples(include_str!("../README.md"));
+        Day10::parse(&examples[0].input).unwrap()
+    }
+
+    #[test]
+    fn parse_rejects_unknown_instructions() {
+        let err = Day10::parse("noop\naddx\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "addx"));
+        let err = Day10::parse("noop\naddx 1x\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 6, "1x"));
+    }
+
+    #[test]
+    fn test() {
+        let day = example();
+        let register = day.run();
+        assert_eq!(register.sum, 13140);
+
+        let expected_screen = [
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ].join("\n");
+        let actual_screen: Screen = Register::make_screen(&register.stream);
+
+        assert_eq!(actual_screen, Grid::parse(&expected_screen, "a pixel", Some).unwrap());
+        assert_eq!(day.part2(), expected_screen);
+    }
+
+    #[test]
+    fn frames_draw_every_cycle() {
+        let day = example();
+        let screen = day.part2();
+        let frames: Vec<Frame> = day.frames(2).collect();
+        assert_eq!(frames.len(), 240);
+        assert_eq!(frames[2].grid.row(0)[..4], ['#', '#', '.', ' ']);
+        assert_eq!(frames[2].focus, Some((2, 0)));
+        assert_eq!(frames[19].caption, "cycle 20: X = 21, signal strength sum 420");
+        assert_eq!(frames[239].grid.to_string(), screen);
+    }
+
+    #[test]
+    fn picture_scales_the_screen() {
+        let picture = example().picture(2);
+        assert_eq!((picture.width(), picture.height()), (320, 48));
+        // the first row starts "##..##.."
+        assert_eq!(picture[(15, 7)], Rgb(255, 210, 80));
+        assert_eq!(picture[(16, 0)], Rgb(20, 20, 30));
+    }
+}
@@ -0,0 +1,242 @@
+use aoc_common::parse::pair;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{parse_lines, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    fn score(&self) -> u32 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Loss => 0,
+        }
+    }
+
+    fn codex(outcome: &str) -> Result<Outcome, ParseError> {
+        match outcome {
+            "X" => Ok(Outcome::Loss),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(ParseError::new("unknown outcome", outcome)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Hand {
+    fn codex(hand: &str) -> Result<Hand, ParseError> {
+        match hand {
+            "A" => Ok(Hand::Rock),
+            "B" => Ok(Hand::Paper),
+            "C" => Ok(Hand::Scissors),
+            _ => Err(ParseError::new("unknown hand", hand)),
+        }
+    }
+
+    fn score(&self) -> u32 {
+        match self {
+            Hand::Rock => 1,
+            Hand::Paper => 2,
+            Hand::Scissors => 3,
+        }
+    }
+
+    fn beats(&self) -> Hand {
+        match self {
+            Hand::Rock => Hand::Scissors,
+            Hand::Paper => Hand::Rock,
+            Hand::Scissors => Hand::Paper,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Round {
+    opponent: Hand,
+    outcome: Outcome,
+}
+
+impl Round {
+    fn parse(line: &str) -> Result<Round, ParseError> {
+        let (opponent, outcome) = pair(line, " ")?;
+        Ok(Round {
+            opponent: Hand::codex(opponent).map_err(|err| err.within(line, opponent))?,
+            outcome: Outcome::codex(outcome).map_err(|err| err.within(line, outcome))?,
+        })
+    }
+
+    fn make_my_hand(&self) -> Hand {
+        match self.outcome {
+            Outcome::Draw => self.opponent.clone(),
+            Outcome::Loss => self.opponent.beats(),
+            Outcome::Win => self.opponent.beats().beats(),
+        }
+    }
+
+    fn score(&self) -> u32 {
+        self.make_my_hand().score() + self.outcome.score()
+    }
+
+    // part one misreads the second column (X, Y, Z) as the hand to play
+    fn misread_hand(&self) -> Hand {
+        match self.outcome {
+            Outcome::Loss => Hand::Rock,
+            Outcome::Draw => Hand::Paper,
+            Outcome::Win => Hand::Scissors,
+        }
+    }
+
+    fn misread_score(&self) -> u32 {
+        let mine = self.misread_hand();
+        mine.score() + outcome(&self.opponent, &mine).score()
+    }
+}
+
+fn outcome(opponent: &Hand, mine: &Hand) -> Outcome {
+    if opponent == mine {
+        Outcome::Draw
+    } else if &opponent.beats() == mine {
+        Outcome::Loss
+    } else {
+        Outcome::Win
+    }
+}
+
+pub struct Day2 {
+    rounds: Vec<Round>,
+}
+
+impl Solution for Day2 {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { rounds: parse_lines(input, Round::parse)? })
+    }
+
+    fn part1(&self) -> u32 {
+        self.rounds.iter().map(|round| round.misread_score()).sum()
+    }
+
+    fn part2(&self) -> u32 {
+        self.rounds.iter().map(|round| round.score()).sum()
+    }
+}
+
+// `size` rounds of the strategy guide
+impl Generator for Day2 {
+    const SIZE: usize = 2500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| format!("{} {}\n", rng.choose(&['A', 'B', 'C']), rng.choose(&['X', 'Y', 'Z']))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day2>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day2>();
+    }
+
+    #[test]
+    fn round_converts_chars_to_hands_and_outcome() {
+        let actual = Round::parse("A X").unwrap();
+        let expected = Round { opponent: Hand::Rock, outcome: Outcome::Loss };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rounds_score_the_strategy_guide() {
+        let day = Day2::parse("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(day.part1(), 15);
+        assert_eq!(day.part2(), 12);
+    }
+
+    #[test]
+    fn round_calls_correct_hand_for_win() {
+        let round = Round { opponent: Hand::Rock, outcome: Outcome::Win };
+        assert_eq!(round.make_my_hand(), Hand::Paper);
+    }
+
+    #[test]
+    fn round_calls_correct_hand_for_draw() {
+        let round = Round { opponent: Hand::Rock, outcome: Outcome::Draw };
+        assert_eq!(round.make_my_hand(), Hand::Rock);
+    }
+
+    #[test]
+    fn round_calls_correct_hand_for_loss() {
+        let round = Round { opponent: Hand::Rock, outcome: Outcome::Loss };
+        assert_eq!(round.make_my_hand(), Hand::Scissors);
+    }
+
+    #[test]
+    fn hands_codex_converts_chars_to_hands() {
+        assert_eq!(Hand::codex("A").unwrap(), Hand::Rock);
+        assert_eq!(Hand::codex("B").unwrap(), Hand::Paper);
+        assert_eq!(Hand::codex("C").unwrap(), Hand::Scissors);
+    }
+
+    #[test]
+    fn hand_knows_which_hand_it_beats() {
+        assert_eq!(Hand::Rock.beats(), Hand::Scissors);
+    }
+
+    #[test]
+    fn outcomes_codex_converts_chars_to_hands() {
+        assert_eq!(Outcome::codex("X").unwrap(), Outcome::Loss);
+        assert_eq!(Outcome::codex("Y").unwrap(), Outcome::Draw);
+        assert_eq!(Outcome::codex("Z").unwrap(), Outcome::Win);
+    }
+
+    #[test]
+    fn codex_rejects_unknown_values() {
+        assert_eq!(Hand::codex("X"), Err(ParseError::new("unknown hand", "X")));
+        assert_eq!(Outcome::codex("A"), Err(ParseError::new("unknown outcome", "A")));
+    }
+
+    #[test]
+    fn parse_locates_unknown_values() {
+        let err = Day2::parse("A Y\nB B\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "B"));
+        let err = Day2::parse("A Y\nAY\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn outcome_correcly_calls_draws() {
+        assert_eq!(outcome(&Hand::Rock, &Hand::Rock), Outcome::Draw);
+        assert_eq!(outcome(&Hand::Paper, &Hand::Paper), Outcome::Draw);
+        assert_eq!(outcome(&Hand::Scissors, &Hand::Scissors), Outcome::Draw);
+    }
+
+    #[test]
+    fn outcome_correcly_calls_losses() {
+        assert_eq!(outcome(&Hand::Rock, &Hand::Scissors), Outcome::Loss);
+        assert_eq!(outcome(&Hand::Paper, &Hand::Rock), Outcome::Loss);
+        assert_eq!(outcome(&Hand::Scissors, &Hand::Paper), Outcome::Loss);
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{parse_lines, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+fn find_dupe_items(left: &str, right: &str) -> String {
+    let left_set: HashSet<char> = left.chars().collect();
+    let right_set: HashSet<char> = right.chars().collect();
+    left_set.intersection(&right_set).copied()
+        .collect::<String>()
+}
+
+// the items all three elves carry
+fn shared_items(trio: &[String]) -> String {
+    let intersection = find_dupe_items(&trio[0], &trio[1]);
+    find_dupe_items(&intersection, &trio[2])
+}
+
+fn get_badge(trio: &[String]) -> String {
+    let badges = shared_items(trio);
+    if badges.len() == 1 {
+        badges
+    } else {
+        panic!("Whoops: trio:{:?}  dupes:{:?}", trio, badges)
+    }
+}
+
+fn priority(items: &str) -> u32 {
+    let item = items.chars().next().unwrap();
+    let score = item as u32 - 'A' as u32;
+    if score < 26 { // was uppercase, bump up by 27 points
+        score + 27
+    } else { // was lowercase
+        item as u32 - 'a' as u32 + 1
+    }
+}
+
+// a rucksack is two equally-sized compartments of items, a-z and A-Z, with
+// exactly one item type in both
+fn parse_rucksack(line: &str) -> Result<String, ParseError> {
+    if let Some(i) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+        let end = i + line[i..].chars().next().unwrap().len_utf8();
+        return Err(ParseError::in_line("expected an item, found", line, &line[i..end]));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::in_line("expected an even number of items, found", line, line));
+    }
+    let (left, right) = line.split_at(line.len() / 2);
+    if find_dupe_items(left, right).len() != 1 {
+        return Err(ParseError::in_line("expected one item type in both compartments of", line, line));
+    }
+    Ok(line.into())
+}
+
+fn get_rucksack_item_priority(line: &str) -> u32 {
+    let middle = line.len() / 2;
+    let (left, right) = (&line[0..middle], &line[middle..]);
+    let dupe = find_dupe_items(left, right);
+    if dupe.len() == 1 {
+        priority(&dupe)
+    } else {
+        panic!("Whoops: left:{:?} right:{:?} dupe:{:?}", left, right, dupe)
+    }
+}
+
+pub struct Day3 {
+    rucksacks: Vec<String>,
+}
+
+impl Solution for Day3 {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let rucksacks = parse_lines(input, parse_rucksack)?;
+        // elves come in groups of three, each sharing exactly one badge
+        let lines: Vec<&str> = input.lines().collect();
+        if !rucksacks.len().is_multiple_of(3) {
+            let last = lines[lines.len() - 1];
+            return Err(ParseError::in_line("expected whole groups of three elves, found", last, last).at(lines.len(), last));
+        }
+        for (group, trio) in rucksacks.chunks(3).enumerate() {
+            if shared_items(trio).len() != 1 {
+                let first = lines[3 * group];
+                let message = "expected one badge shared by the group of three starting with";
+                return Err(ParseError::in_line(message, first, first).at(3 * group + 1, first));
+            }
+        }
+        Ok(Self { rucksacks })
+    }
+
+    // sum of all priorities
+    fn part1(&self) -> u32 {
+        self.rucksacks
+            .iter()
+            .map(|line| get_rucksack_item_priority(line))
+            .sum()
+    }
+
+    // sum of all trio priorities
+    fn part2(&self) -> u32 {
+        self.rucksacks
+            .chunks(3)
+            .map(|trio| priority(&get_badge(trio)))
+            .sum()
+    }
+}
+
+// `size` rucksacks, rounded up to whole groups of three elves. Each rucksack's
+// compartments share exactly one item type and each group exactly one badge,
+// as `get_rucksack_item_priority` and `get_badge` insist.
+impl Generator for Day3 {
+    const SIZE: usize = 300;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut input = String::new();
+        for _ in 0..size.div_ceil(3) {
+            let badge = *rng.choose(&items);
+            let offset = rng.below(3);
+            for elf in 0..3 {
+                // each other item is kept from one of the elves, so none is
+                // carried by the whole group
+                let mut allowed: Vec<char> = (0..items.len())
+                    .filter(|&i| items[i] != badge && (i + offset) % 3 != elf)
+                    .map(|i| items[i])
+                    .collect();
+                rng.shuffle(&mut allowed);
+                let shared = if rng.chance(0.1) { badge } else { allowed.pop().unwrap() };
+                let (left_items, right_items) = allowed.split_at(allowed.len() / 2);
+                let length = rng.between(4, 16) as usize;
+                let mut left: Vec<char> = (1..length).map(|_| *rng.choose(left_items)).chain([shared]).collect();
+                let mut right: Vec<char> = (1..length).map(|_| *rng.choose(right_items)).chain([shared]).collect();
+                if shared != badge {
+                    let side = if rng.chance(0.5) { &mut left } else { &mut right };
+                    side[0] = badge;
+                }
+                rng.shuffle(&mut left);
+                rng.shuffle(&mut right);
+                input.extend(left.into_iter().chain(right));
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day3>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day3>();
+    }
+
+    #[test]
+    fn find_dupe_items_finds_all_duplicate_items() {
+        let actual = find_dupe_items("Aacef", "bdefg");
+        assert!(["ef", "fe"].iter().any(|s| s == &actual.as_str()));
+    }
+
+    #[test]
+    fn priority_returns_1_to_26_for_lowercase_char() {
+        assert_eq!(priority("a"), 1);
+        assert_eq!(priority("z"), 26);
+    }
+
+    #[test]
+    fn priority_returns_27_to_52_for_uppercase_char() {
+        assert_eq!(priority("A"), 27);
+        assert_eq!(priority("Z"), 52);
+    }
+
+    #[test]
+    fn parse_rucksack_rejects_bad_items() {
+        assert_eq!(parse_rucksack("abAb"), Ok("abAb".into()));
+        assert_eq!(parse_rucksack("ab-B").unwrap_err().column, 3);
+        assert_eq!(parse_rucksack("abA").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn get_badge_returns_the_char_common_to_the_trio() {
+        let mut trio = vec![
+            "vJrwpWtwJgWrhcsFMMfFFhFp".into(),
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL".into(),
+            "PmmdzqPrVvPwwTWBwg".into(),
+        ];
+        assert_eq!(get_badge(&trio), "r");
+        trio = vec![
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn".into(),
+            "ttgJtRGJQctTZtZT".into(),
+            "CrZsJsPPZsGzwwsLwLmpwMDw".into(),
+        ];
+        assert_eq!(get_badge(&trio), "Z");
+    }
+
+    // found by fuzzing once the targets solved what they parsed
+    #[test]
+    fn parse_rejects_rucksacks_without_one_shared_item() {
+        let err = Day3::parse("ab\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 1, "ab"));
+        assert!(parse_rucksack("abab").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_groups_without_one_badge() {
+        let err = Day3::parse("aa\nbb\n").err().unwrap();
+        assert_eq!((err.line, err.token.as_str()), (2, "bb"));
+        let err = Day3::parse("aa\nbb\ncc\n").err().unwrap();
+        assert_eq!((err.line, err.token.as_str()), (1, "aa"));
+        let err = Day3::parse("aa\naa\naa\naAaA\nAA\nAA\n").err().unwrap();
+        assert_eq!((err.line, err.token.as_str()), (4, "aAaA"));
+        assert_eq!(Day3::parse("aa\naa\naa\n").unwrap().part2(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::parse::parse_pair;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{number, parse_lines, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+#[derive(Debug, PartialEq)]
+struct WorkRange {
+    range: RangeInclusive<usize>,
+}
+
+impl WorkRange {
+    fn parse(input: &str) -> Result<WorkRange, ParseError> {
+        let (start, end) = parse_pair(input, "-", number, number)?;
+        if start > end {
+            return Err(ParseError::in_line("expected a range from low to high, found", input, input));
+        }
+        Ok(WorkRange { range: start..=end })
+    }
+
+    fn superset(&self, other: &Self) -> bool {
+        self.range.contains(other.range.start())
+            && self.range.contains(other.range.end())
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.range.contains(other.range.start())
+            || self.range.contains(other.range.end())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Work {
+    right: WorkRange,
+    left: WorkRange,
+}
+
+impl Work {
+    #[cfg(test)]
+    fn build(left: RangeInclusive<usize>, right: RangeInclusive<usize>) -> Work {
+        Work {
+            left: WorkRange { range: left },
+            right: WorkRange { range: right },
+        }
+    }
+
+    fn parse_line(line: &str) -> Result<Self, ParseError> {
+        let (left, right) = parse_pair(line, ",", WorkRange::parse, WorkRange::parse)?;
+        Ok(Work { left, right })
+    }
+
+    fn has_superset(&self) -> bool {
+        self.left.superset(&self.right) || self.right.superset(&self.left)
+    }
+
+    fn has_overlap(&self) -> bool {
+        self.left.overlaps(&self.right)
+    }
+}
+
+pub struct Day4 {
+    assignments: Vec<Work>,
+}
+
+impl Solution for Day4 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { assignments: parse_lines(input, Work::parse_line)? })
+    }
+
+    // the number of supersets
+    fn part1(&self) -> usize {
+        self.assignments.iter().filter(|work| work.has_superset()).count()
+    }
+
+    // the number of overlaps
+    fn part2(&self) -> usize {
+        self.assignments
+            .iter()
+            .filter(|work| work.has_superset() || work.has_overlap())
+            .count()
+    }
+}
+
+// `size` pairs of section assignments, all within sections 1 to 99
+impl Generator for Day4 {
+    const SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut range = || {
+            let start = rng.between(1, 99);
+            (start, rng.between(start, 99))
+        };
+        (0..size)
+            .map(|_| {
+                let ((a, b), (c, d)) = (range(), range());
+                format!("{}-{},{}-{}\n", a, b, c, d)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod work_tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day4>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day4>();
+    }
+
+    #[test]
+    fn parse_returns_work() {
+        let expected = Work::build(33..=62, 26..=62);
+        assert_eq!(Work::parse_line("33-62,26-62"), Ok(expected));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_pairs() {
+        let err = Work::parse_line("33-62;26-62").unwrap_err();
+        assert_eq!(err.token, "33-62;26-62");
+        let err = Work::parse_line("33-62,26-6x").unwrap_err().at(1, "33-62,26-6x");
+        assert_eq!((err.column, err.token.as_str()), (10, "6x"));
+    }
+
+    #[test]
+    fn has_superset_returns_true_if_either_range_contains_the_other() {
+        let mut input = Work::build(33..=62, 26..=62);
+        assert!(input.has_superset());
+        input = Work::build(26..=62, 33..=62);
+        assert!(input.has_superset());
+        input = Work::build(1..=9, 2..=10);
+        assert!(!input.has_superset());
+    }
+
+    #[test]
+    fn has_overlap_returns_true_if_either_range_overlaps_the_other() {
+        let input = Work::build(33..=62, 62..=78);
+        assert!(input.has_overlap());
+        let mut input = Work::build(62..=78, 33..=62);
+        assert!(input.has_overlap());
+        input = Work::build(1..=10, 11..=15);
+        assert!(!input.has_overlap());
+        input = Work::build(11..=15, 1..=10);
+        assert!(!input.has_overlap());
+    }
+}
+
+#[cfg(test)]
+mod work_range_tests {
+    use super::*;
+
+    #[test]
+    fn parse_returns_work_range() {
+        let expected = WorkRange { range: 33..=62 };
+        assert_eq!(WorkRange::parse("33-62"), Ok(expected));
+        assert_eq!(WorkRange::parse("7-7"), Ok(WorkRange { range: 7..=7 }));
+    }
+
+    #[test]
+    fn parse_rejects_reversed_ranges() {
+        assert_eq!(WorkRange::parse("5-2").unwrap_err().token, "5-2");
+        let err = Work::parse_line("1-3,5-2").unwrap_err().at(1, "1-3,5-2");
+        assert_eq!((err.column, err.token.as_str()), (5, "5-2"));
+    }
+
+    #[test]
+    fn superset_returns_true_if_left_contains_right() {
+        let left = WorkRange { range: 26..=62 };
+        let right = WorkRange { range: 33..=62 };
+        assert!(left.superset(&right));
+        assert!(!right.superset(&left));
+    }
+
+    #[test]
+    fn overlap_returns_true_if_left_overlaps_right() {
+        let left = WorkRange { range: 5..=7 };
+        let mut right = WorkRange { range: 7..=9 };
+        assert!(left.overlaps(&right));
+        right = WorkRange { range: 8..=9 };
+        assert!(!left.overlaps(&right));
+        right = WorkRange { range: 1..=5 };
+        assert!(left.overlaps(&right));
+        right = WorkRange { range: 1..=4 };
+        assert!(!left.overlaps(&right));
+    }
+}
@@ -0,0 +1,377 @@
+use std::iter;
+
+use aoc_common::animation::{Frame, Frames, Simulation};
+use aoc_common::parse::{columns, fields, groups, tokens};
+use aoc_common::random::{Generator, Rng};
+use aoc_common::tracing::{debug, trace};
+use aoc_common::{number, parse_lines, Grid, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+type Stack = Vec<String>;
+type Stacks = Vec<Stack>;
+
+// the drawing of the crates, ending with the line numbering the stacks
+fn parse(drawing: &str) -> Result<Stacks, ParseError> {
+    let (crates, labels) = drawing.rsplit_once('\n').unwrap_or(("", drawing));
+    let count = parse_labels(labels).map_err(|err| err.at(crates.lines().count() + 1, labels))?;
+    let rows = parse_lines(crates, parse_crate_line)?;
+    for (index, (row, line)) in rows.iter().zip(crates.lines()).enumerate() {
+        if row.iter().skip(count).any(Option::is_some) {
+            let column = columns(line, 4)[count].trim();
+            return Err(ParseError::in_line("no stack is numbered under the crate", line, column).at(index + 1, line));
+        }
+    }
+    let mut stacks: Stacks = vec![Vec::new(); count];
+    for row in rows.iter().rev() {
+        for (stack, content) in stacks.iter_mut().zip(row) {
+            if let Some(content) = content {
+                stack.push(content.clone());
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+// the stacks' numbers, which run from 1 left to right; returns how many
+// stacks there are
+fn parse_labels(line: &str) -> Result<usize, ParseError> {
+    let labels = tokens(line);
+    if labels.is_empty() {
+        return Err(ParseError::new("expected the stacks' numbers, found", line));
+    }
+    for (index, &label) in labels.iter().enumerate() {
+        if number::<usize>(label).ok() != Some(index + 1) {
+            return Err(ParseError::in_line(format!("expected stack number {}, found", index + 1), line, label));
+        }
+    }
+    Ok(labels.len())
+}
+
+// each stack is a column 4 wide holding a crate, a single letter in
+// brackets, or nothing
+fn parse_crate_line(line: &str) -> Result<Vec<Option<String>>, ParseError> {
+    columns(line, 4)
+        .into_iter()
+        .map(|column| {
+            let column = column.trim();
+            if column.is_empty() {
+                return Ok(None);
+            }
+            match column.strip_prefix('[').and_then(|column| column.strip_suffix(']')) {
+                Some(content) if content.len() == 1 && content.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    Ok(Some(content.into()))
+                }
+                Some(content) => Err(ParseError::in_line("expected a crate, found", line, content)),
+                None => Err(ParseError::in_line("expected a crate, found", line, column)),
+            }
+        })
+        .collect()
+}
+
+fn perform_stack_move(instruction: &Instruction, stacks: &mut Stacks) {
+    for _i in 0..instruction.amount {
+        let crate_ = stacks[instruction.from].pop().unwrap();
+        stacks[instruction.to].push(crate_);
+    }
+}
+
+fn perform_full_move(instruction: &Instruction, stacks: &mut Stacks) {
+    let mut stack: Stack = Vec::with_capacity(instruction.amount);
+    for _i in 0..instruction.amount {
+        let crate_ = stacks[instruction.from].pop().unwrap();
+        stack.push(crate_)
+    }
+    for _i in 0..instruction.amount {
+        let crate_ = stack.pop().unwrap();
+        stacks[instruction.to].push(crate_);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Instruction {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Instruction {
+    const PATTERN: &str = "move <n> from <stack> to <stack>";
+
+    fn parse(line: &str) -> Result<Instruction, ParseError> {
+        let [amount, from, to] = fields(line, Instruction::PATTERN)?;
+        Ok(Instruction {
+            amount: number(amount).map_err(|err| err.within(line, amount))?,
+            from: Instruction::stack(from).map_err(|err| err.within(line, from))?,
+            to: Instruction::stack(to).map_err(|err| err.within(line, to))?,
+        })
+    }
+
+    // stacks are numbered from 1
+    fn stack(token: &str) -> Result<usize, ParseError> {
+        match number::<usize>(token)? {
+            0 => Err(ParseError::new("expected a stack number, found", token)),
+            stack => Ok(stack - 1),
+        }
+    }
+
+    // that both stacks exist and there are enough crates to move, moving them
+    // in `heights` so the next move is checked against what this one leaves
+    fn check(&self, line: &str, heights: &mut [usize]) -> Result<(), ParseError> {
+        let [amount, from, to] = fields(line, Instruction::PATTERN)?;
+        for (stack, token) in [(self.from, from), (self.to, to)] {
+            if stack >= heights.len() {
+                return Err(ParseError::in_line("no such stack", line, token));
+            }
+        }
+        if heights[self.from] < self.amount {
+            let message = format!("not enough crates on stack {} to move", self.from + 1);
+            return Err(ParseError::in_line(message, line, amount));
+        }
+        heights[self.from] -= self.amount;
+        heights[self.to] += self.amount;
+        Ok(())
+    }
+}
+
+fn top_row(stacks: &Stacks) -> String {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last().cloned())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+// the stacks as the puzzle draws them, with room for `height` crates
+fn draw(stacks: &Stacks, height: usize) -> Grid<char> {
+    let mut grid = Grid::new((4 * stacks.len()).saturating_sub(1), height + 1, ' ');
+    for (index, stack) in stacks.iter().enumerate() {
+        for (level, content) in stack.iter().enumerate() {
+            let y = height - 1 - level;
+            for (x, c) in format!("[{}]", content).chars().enumerate() {
+                grid[(4 * index + x, y)] = c;
+            }
+        }
+        for (x, c) in (index + 1).to_string().chars().enumerate() {
+            grid[(4 * index + 1 + x, height)] = c;
+        }
+    }
+    grid
+}
+
+pub struct Day5 {
+    stacks: Stacks,
+    instructions: Vec<Instruction>,
+}
+
+impl Day5 {
+    fn rearrange(&self, perform: fn(&Instruction, &mut Stacks)) -> String {
+        let mut stacks = self.stacks.clone();
+        for instruction in self.instructions.iter() {
+            perform(instruction, &mut stacks);
+            // stacks are numbered from 1, as in the input
+            debug!(amount = instruction.amount, from = instruction.from + 1, to = instruction.to + 1, "moved crates");
+            trace!(?stacks);
+        }
+        let top = top_row(&stacks);
+        debug!(%top, "rearranged every crate");
+        top
+    }
+}
+
+impl Solution for Day5 {
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let groups = groups(input);
+        let Some((drawing, moves)) = groups.split_first() else {
+            return Err(ParseError::new("expected a drawing of the stacks, found", "").at(1, ""));
+        };
+        let stacks = drawing.parse(parse)?;
+
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        let mut instructions = vec![];
+        for group in moves {
+            group.parse(|text| {
+                for (index, line) in text.lines().enumerate() {
+                    let instruction = Instruction::parse(line)
+                        .and_then(|instruction| instruction.check(line, &mut heights).map(|()| instruction))
+                        .map_err(|err| err.at(index + 1, line))?;
+                    instructions.push(instruction);
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(Self { stacks, instructions })
+    }
+
+    fn part1(&self) -> String {
+        self.rearrange(perform_stack_move)
+    }
+
+    fn part2(&self) -> String {
+        self.rearrange(perform_full_move)
+    }
+}
+
+// a frame per move
+impl Simulation for Day5 {
+    fn frames(self, part: u8) -> Frames {
+        let perform: fn(&Instruction, &mut Stacks) = if part == 1 { perform_stack_move } else { perform_full_move };
+        // tall enough for every crate in one stack, so the drawing keeps its size
+        let height = self.stacks.iter().map(|stack| stack.len()).sum();
+        let total = self.instructions.len();
+        let mut stacks = self.stacks;
+        let start = Frame::new(draw(&stacks, height), "the starting stacks");
+        let moves = self.instructions.into_iter().enumerate().map(move |(index, instruction)| {
+            perform(&instruction, &mut stacks);
+            let Instruction { amount, from, to } = instruction;
+            let caption = format!("move {} from {} to {} ({}/{})", amount, from + 1, to + 1, index + 1, total);
+            // the top of the stack the crates went to
+            let focus = (4 * to + 1, height - stacks[to].len());
+            Frame::new(draw(&stacks, height), caption).focus(focus.0, focus.1)
+        });
+        Box::new(iter::once(start).chain(moves))
+    }
+}
+
+// two to nine stacks of crates and `size` moves, each taking at most the
+// crates there are to take
+impl Generator for Day5 {
+    const SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let crates: Vec<char> = ('A'..='Z').collect();
+        let mut heights: Vec<usize> = (0..rng.between(2, 9)).map(|_| rng.below(9)).collect();
+        // any stack may start empty, but some crate has to be there to move
+        if heights.iter().all(|&height| height == 0) {
+            let stack = rng.below(heights.len());
+            heights[stack] = 1;
+        }
+        let tallest = *heights.iter().max().unwrap();
+        let mut input = String::new();
+        for level in (0..tallest).rev() {
+            let row: Vec<String> = heights
+                .iter()
+                .map(|&height| if level < height { format!("[{}]", rng.choose(&crates)) } else { "   ".into() })
+                .collect();
+            input += row.join(" ").trim_end();
+            input.push('\n');
+        }
+        let labels: Vec<String> = (1..=heights.len()).map(|stack| format!(" {} ", stack)).collect();
+        input += &labels.join(" ");
+        input += "\n\n";
+
+        for _ in 0..size {
+            let nonempty: Vec<usize> = (0..heights.len()).filter(|&stack| heights[stack] > 0).collect();
+            let from = *rng.choose(&nonempty);
+            let to = (from + 1 + rng.below(heights.len() - 1)) % heights.len();
+            let amount = rng.between(1, heights[from] as i64) as usize;
+            heights[from] -= amount;
+            heights[to] += amount;
+            input += &format!("move {} from {} to {}\n", amount, from + 1, to + 1);
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day5>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day5>();
+    }
+
+    #[test]
+    fn parse_crates_returns_crates() {
+        let crate_lines = "[Q]     [W]\n[N] [B] [Q]\n[H] [W] [S]\n 1   2   3 ";
+        let expected: Stacks = vec![
+            vec!["H".into(), "N".into(), "Q".into()],
+            vec!["W".into(), "B".into()],
+            vec!["S".into(), "Q".into(), "W".into()],
+        ];
+        assert_eq!(parse(crate_lines), Ok(expected));
+    }
+
+    #[test]
+    fn parse_instructions_returns_number_crate_target() {
+        let input = "move 10 from 2 to 7";
+        let expected = Instruction {
+            amount: 10,
+            from: 1,
+            to: 6,
+        };
+        assert_eq!(Instruction::parse(input), Ok(expected));
+    }
+
+    #[test]
+    fn parse_instructions_rejects_malformed_moves() {
+        let err = Instruction::parse("move 1 form 2 to 3").unwrap_err();
+        assert_eq!(err.column, 1);
+        let err = Instruction::parse("move 1 from 0 to 3").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (13, "0"));
+        let err = Instruction::parse("move x from 1 to 3").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (6, "x"));
+    }
+
+    #[test]
+    fn parse_locates_errors_in_diagram_and_moves() {
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from 3 to 1\n";
+        let err = Day5::parse(input).err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (5, 13, "3"));
+        let err = Day5::parse("[A] [+]\n 1   2 \n\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 6, "+"));
+    }
+
+    #[test]
+    fn parse_counts_stacks_from_their_numbers() {
+        // the rightmost stack starts empty
+        let day = Day5::parse("[A]\n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        assert_eq!(day.stacks, [vec!["A".to_string()], vec![]]);
+        assert_eq!(day.part1(), "A");
+        let err = Day5::parse("[A] [B]\n 1   3 \n\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 6, "3"));
+        let err = Day5::parse("[A] [B]\n 1 \n\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 5, "[B]"));
+        let err = Day5::parse("[A] [B]\n\nmove 1 from 1 to 2\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 1, "[A]"));
+    }
+
+    #[test]
+    fn parse_rejects_moving_more_crates_than_a_stack_holds() {
+        let err = Day5::parse("[A] [B]\n 1   2 \n\nmove 5 from 1 to 2\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (4, 6, "5"));
+        // the second move counts the crate the first one took
+        let input = "[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 3 from 2 to 1\nmove 1 from 1 to 2\n";
+        let err = Day5::parse(input).err().unwrap();
+        assert_eq!((err.line, err.column), (5, 6));
+        assert!(Day5::parse("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 2 to 1\n").is_ok());
+    }
+
+    #[test]
+    fn frames_draw_every_move() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\n";
+        let frames: Vec<Frame> = Day5::parse(input).unwrap().frames(2).collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].grid.rows().skip(3).map(|row| row.iter().collect::<String>()).collect::<Vec<_>>(), [
+            "    [D]    ",
+            "[N] [C]    ",
+            "[Z] [M] [P]",
+            " 1   2   3 ",
+        ]);
+        assert_eq!(frames[2].caption, "move 3 from 1 to 3 (2/2)");
+        assert_eq!(frames[2].grid.column(9).collect::<String>(), "  DNZP3");
+        assert_eq!(frames[2].focus, Some((9, 2)));
+    }
+}
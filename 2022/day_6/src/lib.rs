@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+pub struct Day6 {
+    datastream: String,
+}
+
+impl Solution for Day6 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let datastream = input.trim_end();
+        for (index, line) in datastream.lines().enumerate() {
+            if index > 0 {
+                return Err(ParseError::in_line("expected a single datastream, found", line, line).at(index + 1, line));
+            }
+            if let Some(i) = line.find(|c: char| !c.is_ascii_lowercase()) {
+                let end = i + line[i..].chars().next().unwrap().len_utf8();
+                return Err(ParseError::in_line("expected a letter, found", line, &line[i..end]).at(1, line));
+            }
+        }
+        // which also holds a start-of-packet marker, 4 different letters
+        if find_marker(datastream, 14).is_none() {
+            let message = "expected a start-of-message marker, 14 different letters in a row, in";
+            return Err(ParseError::in_line(message, datastream, datastream).at(1, datastream));
+        }
+        Ok(Self { datastream: datastream.into() })
+    }
+
+    // where the start-of-packet marker ends
+    fn part1(&self) -> usize {
+        // parse made sure there is one
+        let (index, _marker) = find_marker(&self.datastream, 4).unwrap();
+        index
+    }
+
+    // where the start-of-message marker ends
+    fn part2(&self) -> usize {
+        let (index, _marker) = find_marker(&self.datastream, 14).unwrap();
+        index
+    }
+}
+
+fn find_marker(line: &str, length: usize) -> Option<(usize, String)> {
+    let end = line.len().checked_sub(length)?;
+    for i in 0..=end {
+        let slice = &line[i..i+length];
+        let set = slice.chars().collect::<HashSet<char>>();
+        if set.len() == length { return Some((i+length, slice.into())) }
+    }
+
+    None
+}
+
+// a datastream `size` letters long, or 15 if that's too short to hold a
+// start-of-message marker. Most of it repeats a few letters, so markers only
+// turn up where one is put.
+impl Generator for Day6 {
+    const SIZE: usize = 4096;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        let length = size.max(15);
+        // a few of the marker's own letters, so it doesn't run on into them
+        let common = &letters[12..12 + rng.between(2, 6) as usize];
+        let mut stream: Vec<char> = (0..length).map(|_| *rng.choose(common)).collect();
+        let start = rng.below(length - 13);
+        stream[start..start + 14].copy_from_slice(&letters[12..]);
+        stream.into_iter().chain(['\n']).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day6>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day6>();
+    }
+
+    // the straightforward way: a queue of the last `length` letters, checked
+    // for repeats after every letter
+    fn find_marker_with_queue(line: &str, length: usize) -> Option<(usize, String)> {
+        let mut queue: VecDeque<char> = VecDeque::with_capacity(length);
+        let mut set: HashSet<char> = HashSet::with_capacity(length);
+
+        for (i, letter) in line.chars().enumerate() {
+            queue.push_back(letter);
+
+            if queue.len() == length {
+                queue.iter().for_each(|x| { set.insert(*x); });
+                if set.len() == length {
+                    return Some((i+1, String::from(&line[i+1-length..i+1])));
+                }
+                queue.pop_front().unwrap();
+                set.clear();
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn find_marker_agrees_with_a_queue() {
+        let markers = |find: fn(&str, usize) -> Option<(usize, String)>| {
+            move |day: &Day6| (find(&day.datastream, 4), find(&day.datastream, 14))
+        };
+        aoc_common::differential::assert_agree(markers(find_marker_with_queue), markers(find_marker));
+    }
+
+    #[test]
+    fn find_marker_returns_marker() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(find_marker(input, 4), Some((7, "jpqm".into())));
+    }
+
+    #[test]
+    fn parse_rejects_anything_but_letters() {
+        let err = Day6::parse("abcD\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 4, "D"));
+        let err = Day6::parse("abcd\nefgh\n").err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn parse_rejects_datastreams_without_a_marker() {
+        for input in ["", "abcdefghijklm\n", "abcdefghijklmabcd\n"] {
+            let err = Day6::parse(input).err().unwrap();
+            assert_eq!((err.line, err.column), (1, 1), "{:?}", input);
+        }
+        assert!(Day6::parse("abcdefghijklmn\n").is_ok());
+    }
+
+    #[test]
+    fn find_marker_checks_every_window() {
+        assert_eq!(find_marker("", 4), None);
+        assert_eq!(find_marker("abc", 4), None);
+        assert_eq!(find_marker("abcd", 4), Some((4, "abcd".into())));
+        assert_eq!(find_marker("aabcd", 4), Some((5, "abcd".into())));
+    }
+
+    #[test]
+    fn find_marker_returns_marker_of_length_14() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(find_marker(input, 14), Some((19, "qmgbljsphdztnv".into())));
+    }
+}
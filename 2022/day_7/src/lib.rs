@@ -0,0 +1,305 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::parse::tokens;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::tracing::{debug, trace};
+use aoc_common::{number, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+#[derive(Debug)]
+struct Solver {
+    sum: usize,
+    // the directories entered, from the root down, with the size of what has
+    // been listed in each so far
+    path: Vec<(String, usize)>,
+    // of every directory left, by its full path, as the same name can turn up
+    // in many places
+    sizes: HashMap<Vec<String>, usize>,
+    // of every file, which no directory can hold more than
+    total: usize,
+    free_up: Option<usize>,
+}
+
+impl Solver {
+    fn new() -> Self {
+        Self {
+            sum: 0,
+            path: Vec::new(),
+            sizes: HashMap::new(),
+            total: 0,
+            free_up: None,
+        }
+    }
+
+    fn parse(&mut self, line: &str) -> Result<(), ParseError> {
+        let command = Command::parse(line)?;
+        // everything but `cd` happens in the current directory
+        if self.path.is_empty() && !matches!(command, Command::Chdir(_)) {
+            return Err(ParseError::in_line("expected a cd into the first directory, found", line, line));
+        }
+        match command {
+            Command::Chdir(name) => { self.cd(name); },
+            // a directory is sized once it has been entered and left
+            Command::List | Command::Dir(_) => (),
+            Command::File(size) => {
+                self.total = self.total.checked_add(size).ok_or_else(|| {
+                    ParseError::in_line("too much data in all, adding", line, line.split_whitespace().next().unwrap())
+                })?;
+                self.add(size);
+            },
+            Command::Up if self.path.len() == 1 => {
+                return Err(ParseError::in_line("expected a directory to leave, found", line, ".."));
+            }
+            Command::Up => { self.pop(); }
+        }
+        Ok(())
+    }
+
+    fn cd(&mut self, name: String) {
+        debug!(directory = %name, depth = self.path.len() + 1, "pushed directory");
+        self.path.push((name, 0));
+    }
+
+    fn add(&mut self, size: usize) {
+        let (name, listed) = self.path.last_mut().unwrap();
+        trace!(directory = %name, size, "added file");
+        *listed += size;
+    }
+
+    fn pop(&mut self) {
+        let full_path: Vec<String> = self.path.iter().map(|(name, _)| name.clone()).collect();
+        let (name, size) = self.path.pop().unwrap();
+        debug!(directory = %name, size, "popped directory");
+        *self.sizes.entry(full_path).or_default() += size;
+
+        // what is in a directory is in its parent too
+        if let Some((_, parent_size)) = self.path.last_mut() {
+            *parent_size += size;
+        }
+    }
+
+    fn flush(&mut self) {
+        while self.path.len() > 1 {
+            self.pop();
+        }
+        self.sum = self.sizes.values().filter(|&&size| size <= 100_000).sum();
+        self.free_up();
+    }
+
+    fn free_up(&mut self) {
+        const MAXIMUM: usize = 70_000_000;
+        const UPDATE: usize = 30_000_000;
+        // no more than MAXIMUM - UPDATE can stay
+        let needed = self.total.saturating_sub(MAXIMUM - UPDATE);
+        self.free_up = self.sizes.values().copied().filter(|&size| size > needed).min();
+    }
+
+    fn sum(&self) -> usize {
+        self.sum
+    }
+}
+
+pub struct Day7 {
+    solver: Solver,
+}
+
+impl Solution for Day7 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut solver = Solver::new();
+        for (index, line) in input.lines().enumerate() {
+            solver.parse(line).map_err(|err| err.at(index + 1, line))?;
+        }
+        solver.flush();
+        debug!(sum = solver.sum, free_up = ?solver.free_up, "sized every directory");
+        if solver.free_up.is_none() {
+            // the session as a whole is at fault, so point at where it starts
+            let line = input.lines().next().unwrap_or("");
+            return Err(ParseError::in_line("no directory is big enough to free up room for the update, in", line, line).at(1, line));
+        }
+        Ok(Self { solver })
+    }
+
+    // total space of all directories < 100_000 in size
+    fn part1(&self) -> usize {
+        self.solver.sum()
+    }
+
+    // directory's size to free up
+    fn part2(&self) -> usize {
+        // parse made sure there is one
+        self.solver.free_up.unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Up,
+    Chdir(String),
+    List,
+    Dir(String),
+    File(usize),
+}
+
+impl Command {
+    fn parse(line: &str) -> Result<Command, ParseError> {
+        match tokens(line)[..] {
+            ["$", "cd", ".."] => Ok(Command::Up),
+            ["$", "ls"] => Ok(Command::List),
+            ["$", "cd", name] => Ok(Command::Chdir(name.into())),
+            ["$", command, ..] => Err(ParseError::in_line("unknown command", line, command)),
+            ["dir", name] => Ok(Command::Dir(name.into())),
+            [size, _name] => Ok(Command::File(number(size).map_err(|err| err.within(line, size))?)),
+            _ => Err(ParseError::in_line("expected a command or a listing, found", line, line)),
+        }
+    }
+}
+
+// a session exploring `size` directories (at least two) that hold between
+// 41,000,000 and 69,000,000 bytes, so there is always something to delete
+// to make room for the update. Names are short, so the same one turns up in
+// different directories, and inside itself.
+impl Generator for Day7 {
+    const SIZE: usize = 180;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let count = size.max(2);
+        let letters: Vec<char> = ('a'..='e').collect();
+        // a name not yet `taken` in the directory it's for
+        let name = |rng: &mut Rng, taken: &mut HashSet<String>| loop {
+            let name: String = (0..rng.between(1, 3)).map(|_| *rng.choose(&letters)).collect();
+            if taken.insert(name.clone()) {
+                return name;
+            }
+        };
+
+        // directory 0 is the root and 1 always sits in it
+        let mut children = vec![vec![]; count];
+        for child in 1..count {
+            children[rng.below(child)].push(child);
+        }
+        let mut taken = vec![HashSet::new(); count];
+        let mut names = vec![String::from("/"); count];
+        for dir in 0..count {
+            for &child in children[dir].iter() {
+                names[child] = name(rng, &mut taken[dir]);
+            }
+        }
+        // small enough files that, whatever else is there, the big file in
+        // directory 1 is always the one to delete
+        let largest = (30_000_000 / (count * 4)).clamp(1, 300_000) as i64;
+        let mut files: Vec<Vec<(usize, String)>> = (0..count)
+            .map(|dir| {
+                (0..rng.below(5))
+                    .map(|_| (rng.between(1, largest) as usize, format!("{}.{}", name(rng, &mut taken[dir]), name(rng, &mut HashSet::new()))))
+                    .collect()
+            })
+            .collect();
+        let total: usize = files.iter().flatten().map(|(size, _)| size).sum();
+        let big = rng.between(41_000_000, 69_000_000) as usize - total;
+        files[1].push((big, name(rng, &mut taken[1])));
+
+        let mut session = String::new();
+        let mut stack = vec![(0, false)];
+        while let Some((dir, left)) = stack.pop() {
+            if left {
+                session += "$ cd ..\n";
+                continue;
+            }
+            session += &format!("$ cd {}\n$ ls\n", names[dir]);
+            let mut listing: Vec<String> = children[dir].iter().map(|&child| format!("dir {}", names[child])).collect();
+            listing.extend(files[dir].iter().map(|(size, name)| format!("{} {}", size, name)));
+            rng.shuffle(&mut listing);
+            for entry in listing {
+                session += &entry;
+                session.push('\n');
+            }
+            // the session ends wherever it is, without climbing back out
+            for &child in children[dir].iter().rev() {
+                stack.push((child, true));
+                stack.push((child, false));
+            }
+        }
+        session.trim_end_matches("$ cd ..\n").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day7>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day7>();
+    }
+
+    // found by fuzzing, as were the ones below
+    #[test]
+    fn parse_rejects_sessions_with_nothing_big_enough_to_delete() {
+        assert!(Day7::parse("").is_err());
+        assert!(Day7::parse("$ cd /\n").is_err());
+        let err = Day7::parse("$ cd /\n$ ls\n100 a\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (1, 1, "$ cd /"));
+        // only a could be deleted, and it's too small
+        let err = Day7::parse("$ cd /\n$ ls\ndir a\n45000000 c\n$ cd a\n$ ls\n5 b\n").err().unwrap();
+        assert_eq!(err.line, 1);
+        assert_eq!(Day7::parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n50000000 b\n$ cd ..\n$ ls\n").unwrap().part2(), 50_000_000);
+    }
+
+    #[test]
+    fn parse_accepts_directories_inside_ones_of_the_same_name() {
+        let day = Day7::parse("$ cd /\n$ ls\ndir e\n$ cd e\n$ cd e\n$ ls\n5 f\n$ cd ..\n").unwrap();
+        // f is in both e and e/e
+        assert_eq!(day.part1(), 5 + 5);
+    }
+
+    #[test]
+    fn directories_are_told_apart_by_their_whole_path() {
+        // a/b and b sit in different places
+        let input = "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\ndir b\n10 x\n$ cd b\n$ ls\n20 y\n$ cd ..\n$ cd ..\n$ cd b\n$ ls\n30 z\n";
+        let day = Day7::parse(input).unwrap();
+        assert_eq!((day.part1(), day.part2()), (20 + 30 + 30, 20));
+        // as do a, a/b and a/b/a
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\ndir b\n$ cd b\n$ ls\ndir a\n$ cd a\n$ ls\n7 x\n";
+        assert_eq!(Day7::parse(input).unwrap().part1(), 3 * 7);
+    }
+
+    #[test]
+    fn parse_rejects_sessions_outside_any_directory() {
+        let err = Day7::parse("100 a\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        let err = Day7::parse("$ cd /\n$ cd ..\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 6, ".."));
+    }
+
+    #[test]
+    fn parse_rejects_totals_too_large_to_count() {
+        let err = Day7::parse("$ cd /\n$ ls\n18446744073709551615 a\n1 b\n").err().unwrap();
+        assert_eq!((err.line, err.token.as_str()), (4, "1"));
+    }
+
+    #[test]
+    fn parse_returns_commands() {
+        assert_eq!(Command::parse("$ cd .."), Ok(Command::Up));
+        assert_eq!(Command::parse("$ cd /"), Ok(Command::Chdir("/".into())));
+        assert_eq!(Command::parse("$ ls"), Ok(Command::List));
+        assert_eq!(Command::parse("dir a"), Ok(Command::Dir("a".into())));
+        assert_eq!(Command::parse("1000 b.txt"), Ok(Command::File(1000)));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_lines() {
+        assert_eq!(Command::parse("$ rm -rf /").unwrap_err().column, 3);
+        assert_eq!(Command::parse("10k0 b.txt").unwrap_err().token, "10k0");
+        assert_eq!(Command::parse("b.txt").unwrap_err().column, 1);
+    }
+}
@@ -0,0 +1,258 @@
+use aoc_common::grid::ORTHOGONAL;
+use aoc_common::image::{self, Image, Picture, Rgb};
+use aoc_common::random::{Generator, Rng};
+use aoc_common::tracing::{debug, trace};
+use aoc_common::{Grid, ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+pub struct Day8 {
+    grid: Grid<i32>,
+}
+
+impl Solution for Day8 {
+    type Part1 = usize;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "a height", height)?;
+        debug!(width = grid.width(), height = grid.height(), "parsed the heights");
+        trace!("heights:\n{}", grid);
+        Ok(Self { grid })
+    }
+
+    // the number of visible trees
+    fn part1(&self) -> usize {
+        count_visible_trees(&determine_visibility(&self.grid))
+    }
+
+    // the highest scenic value
+    fn part2(&self) -> i32 {
+        max_scenic_value(&determine_scenic_values(&self.grid))
+    }
+}
+
+// part 1 as the visible trees in green, part 2 as a heatmap of scenic values.
+// These are products of distances, so they're drawn on a log scale to keep
+// all but the best few from looking alike.
+impl Picture for Day8 {
+    fn picture(&self, part: u8) -> Image {
+        let picture = match part {
+            1 => image::bitmap(&determine_visibility(&self.grid), Rgb(80, 200, 90), Rgb(20, 40, 25)),
+            _ => image::heatmap(&determine_scenic_values(&self.grid).map(|&value| (value as f64).ln_1p())),
+        };
+        image::scale(&picture, 4)
+    }
+}
+
+fn height(c: char) -> Option<i32> {
+    const RADIX: u32 = 10;
+    c.to_digit(RADIX).map(|height| height as i32)
+}
+
+// modified peak-finding algorithm.  Performance O(nm) where n = #rows, m = #columns
+fn determine_visibility(grid: &Grid<i32>) -> Grid<bool> {
+    let mut vis_grid = Grid::new(grid.width(), grid.height(), false);
+
+    // examine each row
+    for y in 0..grid.height() {
+        mark_row_visibilities(grid.row(y), vis_grid.row_mut(y));
+    }
+
+    mark_column_visibilities(grid, &mut vis_grid);
+    trace!("visible trees:\n{}", vis_grid.render(|&visible| if visible { '#' } else { '.' }));
+
+    vis_grid
+}
+
+fn mark_row_visibilities(row: &[i32], vis_map: &mut [bool]) {
+    const MIN_HEIGHT: i32 = -1;
+
+    // visibility from the left
+    let mut max_height = MIN_HEIGHT;
+    for (index, height) in row.iter().enumerate() {
+        if height > &max_height {
+            vis_map[index] = true;
+            max_height = *height;
+        }
+    }
+
+    // visibility from the right
+    max_height = MIN_HEIGHT;
+    let length = vis_map.len() - 1;
+    for (index, height) in row.iter().rev().enumerate() {
+        if height > &max_height {
+            vis_map[length - index] = true;
+            max_height = *height;
+        }
+    }
+}
+
+// the columns are the rows of the transposed grid
+fn mark_column_visibilities(grid: &Grid<i32>, vis_grid: &mut Grid<bool>) {
+    let columns = grid.transpose();
+    let mut vis_columns = vis_grid.transpose();
+    for x in 0..columns.height() {
+        mark_row_visibilities(columns.row(x), vis_columns.row_mut(x));
+    }
+    *vis_grid = vis_columns.transpose();
+}
+
+fn count_visible_trees(vis_grid: &Grid<bool>) -> usize {
+    vis_grid.iter().filter(|&&is_visible| is_visible).count()
+}
+
+// the product of how far each tree can see in every direction; trees on the
+// edge see nothing one way, so score 0
+fn determine_scenic_values(grid: &Grid<i32>) -> Grid<i32> {
+    Grid::from_fn(grid.width(), grid.height(), |x, y| {
+        let height = grid[(x, y)];
+        ORTHOGONAL
+            .iter()
+            .map(|&step| {
+                let mut trees = 0;
+                for (_, &height1) in grid.ray(x, y, step) {
+                    trees += 1;
+                    if height1 >= height {
+                        break;
+                    }
+                }
+                trees
+            })
+            .product()
+    })
+}
+
+fn max_scenic_value(views_grid: &Grid<i32>) -> i32 {
+    trace!("scenic values:\n{}", views_grid);
+    let best = views_grid.cells().max_by_key(|&(_, &value)| value);
+    if let Some(((x, y), value)) = best {
+        debug!(x, y, value, "best view");
+    }
+    best.map_or(0, |(_, &value)| value)
+}
+
+// a forest `size` trees on a side
+impl Generator for Day8 {
+    const SIZE: usize = 99;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut forest = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            forest.extend((0..size).map(|_| char::from(b'0' + rng.below(10) as u8)));
+            forest.push('\n');
+        }
+        forest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day8>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day8>();
+    }
+
+    #[test]
+    fn pictures_show_visibility_and_scenic_values() {
+        let day = Day8::parse("30373\n25512\n65332\n33549\n35390\n").unwrap();
+        let visible = day.picture(1);
+        assert_eq!((visible.width(), visible.height()), (20, 20));
+        assert_eq!(visible[(4, 4)], Rgb(80, 200, 90));
+        assert_eq!(visible[(8, 8)], Rgb(20, 40, 25));
+        // (2, 3) has the best view
+        let scenic = day.picture(2);
+        assert_eq!(scenic[(8, 12)], image::heat(1.0));
+        assert_eq!(scenic[(0, 0)], image::heat(0.0));
+    }
+
+    #[test]
+    fn test_height() {
+        let heights: Vec<i32> = "13254323".chars().filter_map(height).collect();
+        assert_eq!(heights, [1, 3, 2, 5, 4, 3, 2, 3]);
+        assert_eq!(height('x'), None);
+    }
+
+    #[test]
+    fn parse_rejects_bad_and_ragged_rows() {
+        let err = Day8::parse("303\n2x5\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+        let err = Day8::parse("303\n25\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "25"));
+        assert!(Day8::parse("").is_err());
+    }
+
+    #[test]
+    fn test_mark_visibilities_by_row() {
+        let heights = [1, 3, 2, 5, 4, 3, 2, 3];
+        let mut vis_map = vec![false; heights.len()];
+        mark_row_visibilities(&heights, &mut vis_map);
+        assert_eq!(vis_map, [true, true, false, true, true, false, false, true]);
+        assert_eq!(vis_map.iter().filter(|&&visible| visible).count(), 5);
+    }
+
+    fn input_grid() -> Grid<i32> {
+        Day8::parse("30373\n25512\n65332\n33549\n35390\n").unwrap().grid
+    }
+
+    fn vis_grid(map: &str) -> Grid<bool> {
+        Grid::parse(map, "# or .", |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_mark_visibilities_by_column() {
+        let grid = Grid::from_vec(1, vec![3, 2, 6, 3, 3]);
+        let mut vis_grid = Grid::new(1, 5, false);
+        mark_column_visibilities(&grid, &mut vis_grid);
+
+        assert_eq!(
+            vis_grid.iter().copied().collect::<Vec<_>>(),
+            [true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_determine_visibility() {
+        let actual = determine_visibility(&input_grid());
+        assert_eq!(actual, vis_grid("#####\n###.#\n##.##\n#.#.#\n#####\n"));
+    }
+
+    #[test]
+    fn test_count_visible_trees() {
+        let vis_grid = vis_grid("#####\n###.#\n##.##\n#.#.#\n#####\n");
+        assert_eq!(count_visible_trees(&vis_grid), 21);
+    }
+
+    // a tree is visible if every tree on some ray out to the edge is shorter
+    fn visibility_by_looking(grid: &Grid<i32>) -> Grid<bool> {
+        Grid::from_fn(grid.width(), grid.height(), |x, y| {
+            ORTHOGONAL.iter().any(|&step| grid.ray(x, y, step).all(|(_, &height)| height < grid[(x, y)]))
+        })
+    }
+
+    #[test]
+    fn visibility_agrees_with_looking_from_every_tree() {
+        aoc_common::differential::assert_agree(
+            |day: &Day8| visibility_by_looking(&day.grid),
+            |day: &Day8| determine_visibility(&day.grid),
+        );
+    }
+
+    #[test]
+    fn test_determine_scenic_values() {
+        let grid = input_grid();
+        assert_eq!(
+            determine_scenic_values(&grid).to_string(),
+            "00000\n01410\n06120\n01830\n00000"
+        );
+        assert_eq!(max_scenic_value(&determine_scenic_values(&grid)), 8);
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+use std::iter;
+
+use aoc_common::animation::{Frame, Frames, Simulation};
+use aoc_common::image::{self, Image, Picture, Rgb};
+use aoc_common::parse::pair;
+use aoc_common::random::{Generator, Rng};
+use aoc_common::{number, parse_lines, Bounds, Direction, Grid, ParseError, Point, Solution, Vec2};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+#[derive(Debug, PartialEq)]
+struct Rope {
+    knots: Vec<Point>,
+    visited: HashSet<Point>,
+}
+
+impl Rope {
+    fn new(length: usize) -> Self {
+        Self {
+            knots: vec![Point::ORIGIN; length],
+            visited: HashSet::from([Point::ORIGIN]),
+        }
+    }
+
+    fn travel(&mut self, instruction: &Instruction) {
+        let step = instruction.direction.step();
+        for _ in 0..instruction.distance {
+            self.step(step);
+        }
+    }
+
+    fn step(&mut self, step: Vec2) {
+        let tail_i = self.knots.len() - 1;
+
+        // update head
+        self.knots[0] += step;
+
+        // update subsequent knots
+        for i in 1..self.knots.len() {
+            let gap = self.knots[i - 1] - self.knots[i];
+            if gap.chebyshev() <= 1 {
+                // still touching, so the rest of the rope stays put
+                break;
+            }
+            self.knots[i] += gap.signum();
+            if i == tail_i { // i.e. is tail. register its new position
+                self.visited.insert(self.knots[tail_i]);
+            }
+        }
+    }
+
+    // the rope within `bounds` as the puzzle draws it: H for the head, then
+    // T or 1 to 9 for the knots, s for the start and # where the tail has been
+    fn draw(&self, bounds: Bounds) -> Grid<char> {
+        let mut grid = Grid::new(bounds.width(), bounds.height(), '.');
+        let cell = |point: Point| ((point.x - bounds.min.x) as usize, (point.y - bounds.min.y) as usize);
+        for &point in self.visited.iter() {
+            grid[cell(point)] = '#';
+        }
+        grid[cell(Point::ORIGIN)] = 's';
+        // knots nearer the head cover the ones behind them
+        for (i, &knot) in self.knots.iter().enumerate().rev() {
+            grid[cell(knot)] = match i {
+                0 => 'H',
+                _ if self.knots.len() == 2 => 'T',
+                _ => char::from_digit(i as u32, 36).unwrap(),
+            };
+        }
+        grid
+    }
+}
+
+struct Instruction {
+    direction: Direction,
+    distance: usize,
+}
+
+impl Instruction {
+    fn new(direction: Direction, distance: usize) -> Self {
+        Self {
+            direction,
+            distance,
+        }
+    }
+
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let (direction, distance) = pair(line, " ")?;
+        Ok(Self::new(
+            Instruction::direction(direction).map_err(|err| err.within(line, direction))?,
+            number(distance).map_err(|err| err.within(line, distance))?,
+        ))
+    }
+
+    // only the four capitals the puzzle uses, not every spelling `Direction`
+    // parses
+    fn direction(token: &str) -> Result<Direction, ParseError> {
+        match token {
+            "U" => Ok(Direction::Up),
+            "R" => Ok(Direction::Right),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            _ => Err(ParseError::new("expected U, R, D or L, found", token)),
+        }
+    }
+}
+
+pub struct Day9 {
+    instructions: Vec<Instruction>,
+}
+
+impl Day9 {
+    // a rope of `length` knots after every instruction
+    fn travelled(&self, length: usize) -> Rope {
+        let mut rope = Rope::new(length);
+        for instruction in self.instructions.iter() {
+            rope.travel(instruction);
+        }
+        rope
+    }
+
+    fn visited(&self, length: usize) -> usize {
+        self.travelled(length).visited.len()
+    }
+}
+
+// Every position the head reaches. The other knots follow the head, so they
+// stay within these bounds too.
+fn head_bounds(instructions: &[Instruction]) -> Bounds {
+    let mut head = Point::ORIGIN;
+    let mut bounds = Bounds::at(head);
+    for instruction in instructions.iter() {
+        head += instruction.direction.step() * instruction.distance as isize;
+        bounds = bounds.including(head);
+    }
+    bounds
+}
+
+impl Solution for Day9 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { instructions: parse_lines(input, Instruction::parse)? })
+    }
+
+    // positions visited by the tail of a 2-knot rope
+    fn part1(&self) -> usize {
+        self.visited(2)
+    }
+
+    // positions visited by the tail of a 10-knot rope
+    fn part2(&self) -> usize {
+        self.visited(10)
+    }
+}
+
+// where the tail has been, in white, with the start in red
+impl Picture for Day9 {
+    fn picture(&self, part: u8) -> Image {
+        let visited = self.travelled(if part == 1 { 2 } else { 10 }).visited;
+        let bounds = Bounds::of(visited.iter().copied()).unwrap_or(Bounds::at(Point::ORIGIN));
+        let mut picture = Grid::new(bounds.width(), bounds.height(), Rgb::BLACK);
+        for point in visited.into_iter().chain([Point::ORIGIN]) {
+            let colour = if point == Point::ORIGIN { Rgb(230, 60, 50) } else { Rgb::WHITE };
+            picture[((point.x - bounds.min.x) as usize, (point.y - bounds.min.y) as usize)] = colour;
+        }
+        image::scale(&picture, 2)
+    }
+}
+
+// a frame per step of the head
+impl Simulation for Day9 {
+    fn frames(self, part: u8) -> Frames {
+        let mut rope = Rope::new(if part == 1 { 2 } else { 10 });
+        let bounds = head_bounds(&self.instructions);
+        let steps = self
+            .instructions
+            .into_iter()
+            .flat_map(|instruction| iter::repeat_n(instruction.direction, instruction.distance));
+        Box::new(steps.enumerate().map(move |(index, direction)| {
+            rope.step(direction.step());
+            let head = rope.knots[0];
+            let caption = format!("step {}: the tail has visited {} positions", index + 1, rope.visited.len());
+            let focus = ((head.x - bounds.min.x) as usize, (head.y - bounds.min.y) as usize);
+            Frame::new(rope.draw(bounds), caption).focus(focus.0, focus.1)
+        }))
+    }
+}
+
+// `size` motions of the head, each up to 20 steps long
+impl Generator for Day9 {
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| format!("{} {}\n", rng.choose(&['U', 'R', 'D', 'L']), rng.between(1, 20))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day9>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        aoc_common::random::assert_generates::<Day9>();
+    }
+
+    #[test]
+    fn parse_rejects_unknown_motions() {
+        let err = Day9::parse("R 4\nX 4\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "X"));
+        let err = Day9::parse("R 4\nU -4\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "-4"));
+        for direction in ["u", "N", "E", "S", "W", "↑"] {
+            let err = Day9::parse(&format!("R 4\n{} 4\n", direction)).err().unwrap();
+            assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, direction));
+        }
+    }
+
+    #[test]
+    fn test_2_knot_rope_travelling() {
+        let mut rope = Rope::new(2);
+
+        rope.travel(&Instruction::new(Direction::Right, 4));
+        let mut expected = Rope {
+            knots: vec![Point::new(4, 0), Point::new(3, 0)],
+            visited: HashSet::from([
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
+            ]),
+        };
+        assert_eq!(rope, expected);
+
+        rope.travel(&Instruction::new(Direction::Up, 4));
+        expected = Rope {
+            knots: vec![Point::new(4, -4), Point::new(4, -3)],
+            visited: HashSet::from([
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
+                Point::new(4, -1),
+                Point::new(4, -2),
+                Point::new(4, -3),
+            ]),
+        };
+        assert_eq!(rope, expected);
+
+        rope.travel(&Instruction::new(Direction::Left, 3));
+        expected = Rope {
+            knots: vec![Point::new(1, -4), Point::new(2, -4)],
+            visited: HashSet::from([
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
+                Point::new(4, -1),
+                Point::new(4, -2),
+                Point::new(4, -3),
+                Point::new(3, -4),
+                Point::new(2, -4),
+            ]),
+        };
+        assert_eq!(rope, expected);
+
+        rope.travel(&Instruction::new(Direction::Down, 1));
+        expected = Rope {
+            knots: vec![Point::new(1, -3), Point::new(2, -4)],
+            visited: HashSet::from([
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
+                Point::new(4, -1),
+                Point::new(4, -2),
+                Point::new(4, -3),
+                Point::new(3, -4),
+                Point::new(2, -4),
+            ]),
+        };
+        assert_eq!(rope, expected);
+    }
+
+    #[test]
+    fn picture_shows_the_visited_positions() {
+        let picture = Day9::parse("R 4\nU 4\n").unwrap().picture(1);
+        assert_eq!((picture.width(), picture.height()), (10, 8));
+        let unscaled = Grid::from_fn(5, 4, |x, y| picture[(x * 2, y * 2)]);
+        let drawn = unscaled.render(|&pixel| match pixel {
+            Rgb::WHITE => '#',
+            Rgb::BLACK => '.',
+            _ => 's',
+        });
+        assert_eq!(drawn, "....#\n....#\n....#\ns###.");
+    }
+
+    #[test]
+    fn frames_draw_every_step() {
+        let frames: Vec<Frame> = Day9::parse("R 2\nU 2\n").unwrap().frames(1).collect();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].grid.to_string(), "...\n...\nTH.");
+        assert_eq!(frames[3].grid.to_string(), "..H\n..T\ns#.");
+        assert_eq!(frames[3].caption, "step 4: the tail has visited 3 positions");
+        assert_eq!(frames[3].focus, Some((2, 0)));
+        let last = Day9::parse("R 4\nU 4\n").unwrap().frames(2).last().unwrap();
+        assert_eq!(last.grid.to_string(), "....H\n....1\n..432\n.5...\n6....");
+    }
+}
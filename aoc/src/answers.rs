@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Where the known answers live, relative to this crate.
+pub const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+
+// Accepted answers, keyed by year, day and part.
+pub type KnownAnswers = BTreeMap<(u16, u8, u8), String>;
+
+// One answer per line: `<year> <day> <part> <answer>`. Answers spanning
+// several lines (e.g. a rendered screen) have their newlines written as \n.
+pub fn parse(text: &str) -> Result<KnownAnswers, String> {
+    let mut known = KnownAnswers::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("line {}: expected <year> <day> <part> <answer>", index + 1);
+        let mut fields = line.splitn(4, ' ');
+        let mut key = || fields.next().and_then(|field| field.parse::<u16>().ok()).ok_or_else(invalid);
+        let (year, day, part) = (key()?, key()?, key()?);
+        let answer = fields.next().filter(|answer| !answer.is_empty()).ok_or_else(invalid)?;
+        if day > 25 || !(1..=2).contains(&part) {
+            return Err(invalid());
+        }
+        known.insert((year, day as u8, part as u8), answer.replace("\\n", "\n"));
+    }
+    Ok(known)
+}
+
+pub fn format(known: &KnownAnswers) -> String {
+    let mut out = String::from("# year day part answer (newlines in answers are written as \\n)\n");
+    for ((year, day, part), answer) in known.iter() {
+        writeln!(out, "{} {} {} {}", year, day, part, answer.replace('\n', "\\n")).unwrap();
+    }
+    out
+}
+
+pub fn load(path: &Path) -> io::Result<KnownAnswers> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::new()),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn save(path: &Path, known: &KnownAnswers) -> io::Result<()> {
+    fs::write(path, format(known))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+pub fn check(known: &KnownAnswers, key: (u16, u8, u8), actual: &str) -> Verdict {
+    match known.get(&key) {
+        Some(expected) if expected == actual => Verdict::Correct,
+        Some(expected) => Verdict::Wrong { expected: expected.clone() },
+        None => Verdict::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn parse_reads_answers_and_multiline_answers() {
+        let known = parse("# comment\n2022 9 1 6175\n2022 10 2 #..#\\n.##.\n").unwrap();
+        assert_eq!(known[&(2022, 9, 1)], "6175");
+        assert_eq!(known[&(2022, 10, 2)], "#..#\n.##.");
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        assert!(parse("2022 9 6175").is_err());
+        assert!(parse("2022 9 3 6175").is_err());
+        assert!(parse("2022 nine 1 6175").is_err());
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let known = parse("2022 10 2 #..#\\n.##.\n2015 1 1 232\n").unwrap();
+        assert_eq!(parse(&format(&known)).unwrap(), known);
+    }
+
+    #[test]
+    fn check_compares_against_known_answers() {
+        let known = parse("2022 9 1 6175\n").unwrap();
+        assert_eq!(check(&known, (2022, 9, 1), "6175"), Verdict::Correct);
+        assert_eq!(check(&known, (2022, 9, 1), "6176"), Verdict::Wrong { expected: "6175".into() });
+        assert_eq!(check(&known, (2022, 9, 2), "2578"), Verdict::Unknown);
+    }
+
+    // The same check as `aoc verify --all`, so `cargo test` catches drift too.
+    #[test]
+    fn every_registered_day_gives_its_known_answers() {
+        let known = parse(include_str!("../../answers.txt")).unwrap();
+        for day in DAYS.iter() {
+            let answers = (day.solve)(day.input).unwrap();
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                assert_eq!(
+                    check(&known, (day.year, day.day, part), &answer.value),
+                    Verdict::Correct,
+                    "{} day {} part {}",
+                    day.year,
+                    day.day,
+                    part,
+                );
+            }
+        }
+    }
+}
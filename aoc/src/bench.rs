@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_common::bench::{Bench, Stats};
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub bench: Bench,
+}
+
+impl Row {
+    fn steps(&self) -> [(&'static str, &Stats); 3] {
+        [("parse", &self.bench.parse), ("1", &self.bench.part1), ("2", &self.bench.part2)]
+    }
+}
+
+// Median timings of a previous run, keyed by year, day and step
+// ("parse", "1" or "2").
+pub type Baseline = HashMap<(u16, u8, String), Duration>;
+
+// Baseline files hold one step per line: `<year> <day> <step> <median in ns>`.
+pub fn load(path: &Path) -> io::Result<Baseline> {
+    let mut baseline = Baseline::new();
+    for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: expected <year> <day> <step> <nanoseconds>", path.display(), index + 1),
+            )
+        };
+        match line.split_whitespace().collect::<Vec<&str>>()[..] {
+            [year, day, step, nanos] => {
+                let year = year.parse().map_err(|_| invalid())?;
+                let day = day.parse().map_err(|_| invalid())?;
+                let nanos = nanos.parse().map_err(|_| invalid())?;
+                baseline.insert((year, day, step.into()), Duration::from_nanos(nanos));
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(baseline)
+}
+
+pub fn save(path: &Path, rows: &[Row]) -> io::Result<()> {
+    let mut out = String::from("# year day step median_ns\n");
+    for row in rows.iter() {
+        for (step, stats) in row.steps() {
+            writeln!(out, "{} {} {} {}", row.year, row.day, step, stats.median.as_nanos()).unwrap();
+        }
+    }
+    fs::write(path, out)
+}
+
+// How a median compares to its baseline, in percent (positive is slower).
+fn change(median: Duration, baseline: Duration) -> f64 {
+    let baseline = baseline.as_nanos().max(1) as f64;
+    (median.as_nanos() as f64 - baseline) / baseline * 100.0
+}
+
+// Lays out every step's statistics in a table. With a baseline, each step
+// is compared against it and marked when slower by more than `threshold`
+// percent. Returns the table and the number of regressions.
+pub fn table(rows: &[Row], baseline: Option<&Baseline>, threshold: f64) -> (String, usize) {
+    let mut out = String::new();
+    let mut regressions = 0;
+    write!(out, "year  day   part  {:>10}  {:>10}  {:>10}  {:>10}", "mean", "median", "stddev", "min").unwrap();
+    if baseline.is_some() {
+        write!(out, "  {:>10}", "baseline").unwrap();
+    }
+    out.push('\n');
+
+    for row in rows.iter() {
+        for (step, stats) in row.steps() {
+            write!(
+                out,
+                "{:>4}  {:>3}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+                row.year,
+                row.day,
+                step,
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.stddev),
+                format!("{:.2?}", stats.min),
+            )
+            .unwrap();
+            if let Some(baseline) = baseline {
+                match baseline.get(&(row.year, row.day, step.to_string())) {
+                    Some(previous) => {
+                        let change = change(stats.median, *previous);
+                        write!(out, "  {:>+9.1}%", change).unwrap();
+                        if change > threshold {
+                            out += "  REGRESSED";
+                            regressions += 1;
+                        }
+                    }
+                    None => write!(out, "  {:>10}", "-").unwrap(),
+                }
+            }
+            out.push('\n');
+        }
+    }
+
+    (out, regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn stats(median_ms: u64) -> Stats {
+        let median = Duration::from_millis(median_ms);
+        Stats { samples: 1, mean: median, median, stddev: Duration::ZERO, min: median }
+    }
+
+    fn row(parse: u64, part1: u64, part2: u64) -> Row {
+        Row { year: 2022, day: 8, bench: Bench { parse: stats(parse), part1: stats(part1), part2: stats(part2) } }
+    }
+
+    #[test]
+    fn save_then_load_round_trips_medians() {
+        let path = env::temp_dir().join(format!("aoc_bench_{}", std::process::id()));
+        save(&path, &[row(1, 2, 3)]).unwrap();
+        let baseline = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(baseline.len(), 3);
+        assert_eq!(baseline[&(2022, 8, "2".to_string())], Duration::from_millis(3));
+    }
+
+    #[test]
+    fn table_flags_steps_slower_than_the_threshold() {
+        let mut baseline = Baseline::new();
+        baseline.insert((2022, 8, "parse".into()), Duration::from_millis(1));
+        baseline.insert((2022, 8, "1".into()), Duration::from_millis(10));
+        baseline.insert((2022, 8, "2".into()), Duration::from_millis(10));
+
+        let (table, regressions) = table(&[row(1, 20, 9)], Some(&baseline), 10.0);
+        assert_eq!(regressions, 1);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].ends_with("+0.0%"));
+        assert!(lines[2].ends_with("+100.0%  REGRESSED"));
+        assert!(lines[3].ends_with("-10.0%"));
+    }
+
+    #[test]
+    fn table_without_baseline_has_no_comparison() {
+        let (table, regressions) = table(&[row(1, 2, 3)], None, 10.0);
+        assert_eq!(regressions, 0);
+        assert!(!table.contains("baseline"));
+        assert_eq!(table.lines().count(), 4);
+    }
+}
@@ -0,0 +1,578 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc_common::bench::Options;
+use aoc_common::image::{Recording, MAX_GIF_FPS};
+
+use crate::report::Format;
+use crate::trace::{self, Directive};
+
+pub const USAGE: &str = "\
+usage: aoc run <selection> [--input <path> | --input -] [--format <format>]
+               [--jobs <n>] [--render <file>] [--trace <directives>]
+       aoc bench <selection> [--samples <n>] [--warmup <n>]
+                 [--save <file>] [--baseline <file>] [--threshold <percent>]
+       aoc verify <selection> [--answers <file>] [--record]
+       aoc profile <selection>
+       aoc new <year> <day>
+       aoc fetch <selection> [--url <base url>]
+       aoc submit <year> <day> <part> [--input <path> | --answer <answer>]
+                  [--url <base url>]
+       aoc watch <year> <day> [--part <part>] [--fps <n>] [--input <path>]
+                 [--record <file.gif> [--scale <n>] [--every <n>]]
+       aoc gen <year> <day> [--seed <n>] [--size <n>]
+
+where <selection> is one of
+       <year> <day>     a single day
+       <year> --all     every day of a year
+       --all            every registered day
+
+By default each day runs against its own embedded input. --input reads a
+single day's input from a file instead, or from stdin when given \"-\".
+--format prints the answers as a table (the default), json or csv, with
+timings in nanoseconds. Days run in parallel on --jobs worker threads (by
+default one per core); the report shows the wall-clock time as well as the
+time summed over every step. --render draws a single day's parts as images,
+for days that can (2022 days 8, 9 and 10): out.png becomes out-1.png and
+out-2.png, and a .ppm name writes PPM instead of PNG. --trace prints the
+solvers' events to stderr, as comma-separated directives like day7=debug:
+a day and the most detailed level to show (error, warn, info, debug or
+trace; debug if left out), or just a level for every day.
+
+bench times parsing and each part separately. --save writes the medians to a
+baseline file; --baseline compares against one and fails if any step got
+slower by more than --threshold percent (default 10).
+
+profile counts the heap allocations of parsing and each part: how many,
+how many bytes in all, and the most held at once. It needs the counting
+allocator, which is only built in with --features alloc-profile.
+
+verify checks every answer against the known answers (answers.txt by
+default) and fails if any differ. --record adds the answers of days that have
+no known answers yet.
+
+new creates <year>/day_<day> from templates/rust and registers it with the
+workspace and this runner.
+
+fetch downloads each day's puzzle input into its directory, unless it is
+already there. It logs in with the session cookie from AOC_SESSION or the
+.session file at the root of the repository.
+
+submit solves a day and posts one part's answer (or the one given by
+--answer, e.g. letters read off a rendered screen). Correct answers are added
+to the known answers. Rejected answers are remembered in submissions.txt and
+never submitted again, nor are numbers beyond a rejected too high or too low
+one, nor anything while the server has asked to wait.
+
+watch plays a day's simulation in the terminal, a frame per step, for days
+that have one (2022 days 5, 9 and 10). It plays part 1 at 10 frames a second
+unless --part and --fps say otherwise. Space pauses, the right arrow or n
+steps a frame at a time, + and - change the speed and q quits. --record
+writes the frames to an animated GIF instead, at up to 50 frames a second,
+with each cell --scale pixels wide (default 4), keeping only every --every'th
+frame (and the last) to shorten long simulations.
+
+gen prints a random input for a day, for trying its solution on more than
+its one real input. The same --seed always gives the same input; without
+one a seed is picked and shown on stderr. --size sets how big the input is,
+in the day's own terms: lines, moves, rows of a grid and so on.";
+
+#[derive(Debug, PartialEq)]
+pub struct Selection {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        input: Option<String>,
+        format: Format,
+        jobs: Option<usize>,
+        render: Option<PathBuf>,
+        trace: Vec<Directive>,
+    },
+    Bench {
+        selection: Selection,
+        options: Options,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        threshold: f64,
+    },
+    Verify { selection: Selection, answers: Option<PathBuf>, record: bool },
+    Profile { selection: Selection },
+    New { year: u16, day: u8 },
+    Fetch { selection: Selection, url: Option<String> },
+    Submit {
+        year: u16,
+        day: u8,
+        part: u8,
+        input: Option<String>,
+        answer: Option<String>,
+        url: Option<String>,
+    },
+    Watch { year: u16, day: u8, part: u8, fps: u32, input: Option<String>, record: Option<(PathBuf, Recording)> },
+    Gen { year: u16, day: u8, seed: Option<u64>, size: Option<usize> },
+    Help,
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run(scan(args, &[], &["--input", "--format", "--jobs", "--render", "--trace"])?),
+        Some("bench") => parse_bench(scan(
+            args,
+            &[],
+            &["--samples", "--warmup", "--save", "--baseline", "--threshold"],
+        )?),
+        Some("verify") => parse_verify(scan(args, &["--record"], &["--answers"])?),
+        Some("profile") => Ok(Command::Profile { selection: scan(args, &[], &[])?.selection()? }),
+        Some("new") => parse_new(scan(args, &[], &[])?),
+        Some("submit") => parse_submit(scan(args, &[], &["--input", "--answer", "--url"])?),
+        Some("watch") => {
+            parse_watch(scan(args, &[], &["--part", "--fps", "--input", "--record", "--scale", "--every"])?)
+        }
+        Some("gen") => parse_gen(scan(args, &[], &["--seed", "--size"])?),
+        Some("fetch") => {
+            let args = scan(args, &[], &["--url"])?;
+            Ok(Command::Fetch { selection: args.selection()?, url: args.values.get("--url").cloned() })
+        }
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command {:?}", other)),
+    }
+}
+
+fn parse_run(args: Args) -> Result<Command, String> {
+    let input = args.values.get("--input").cloned();
+    if args.all && input.is_some() {
+        return Err("--input needs a single day, not --all".into());
+    }
+    let format = match args.values.get("--format").map(String::as_str) {
+        None | Some("table") => Format::Table,
+        Some("json") => Format::Json,
+        Some("csv") => Format::Csv,
+        Some(other) => return Err(format!("unknown format {:?}: expected table, json or csv", other)),
+    };
+    let jobs = args.values.get("--jobs").map(|jobs| number(jobs, "--jobs")).transpose()?;
+    if jobs == Some(0) {
+        return Err("--jobs needs at least one worker".into());
+    }
+    let selection = args.selection()?;
+    let render = args.values.get("--render").map(PathBuf::from);
+    if let Some(path) = &render {
+        if selection.day.is_none() {
+            return Err("--render needs a single day, not --all".into());
+        }
+        if !matches!(path.extension().and_then(|extension| extension.to_str()), Some("png" | "ppm")) {
+            return Err(format!("can't render to {}: expected a .png or .ppm file", path.display()));
+        }
+    }
+    let trace = args.values.get("--trace").map(|spec| trace::parse(spec)).transpose()?.unwrap_or_default();
+    Ok(Command::Run { selection, input, format, jobs, render, trace })
+}
+
+// Where `--render path` puts `part`'s image: "out.png" becomes "out-1.png".
+pub fn part_path(path: &Path, part: u8) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}-{}.{}", stem, part, extension))
+}
+
+fn parse_bench(args: Args) -> Result<Command, String> {
+    let defaults = Options::default();
+    let samples = args.value("--samples", defaults.samples)?;
+    if samples == 0 {
+        return Err("--samples needs at least one sample".into());
+    }
+    Ok(Command::Bench {
+        selection: args.selection()?,
+        options: Options { warmup: args.value("--warmup", defaults.warmup)?, samples },
+        save: args.values.get("--save").map(PathBuf::from),
+        baseline: args.values.get("--baseline").map(PathBuf::from),
+        threshold: args.value("--threshold", 10.0)?,
+    })
+}
+
+fn parse_verify(args: Args) -> Result<Command, String> {
+    Ok(Command::Verify {
+        selection: args.selection()?,
+        answers: args.values.get("--answers").map(PathBuf::from),
+        record: args.switches.contains("--record"),
+    })
+}
+
+fn parse_new(args: Args) -> Result<Command, String> {
+    match (args.positional.as_slice(), args.all) {
+        ([year, day], false) => Ok(Command::New { year: number(year, "year")?, day: day_number(day)? }),
+        _ => Err("new expects a year and a day".into()),
+    }
+}
+
+fn parse_submit(args: Args) -> Result<Command, String> {
+    let ([year, day, part], false) = (args.positional.as_slice(), args.all) else {
+        return Err("submit expects a year, a day and a part".into());
+    };
+    let part = part_number(part)?;
+    let input = args.values.get("--input").cloned();
+    let answer = args.values.get("--answer").cloned();
+    if input.is_some() && answer.is_some() {
+        return Err("--answer replaces solving, so it can't be used with --input".into());
+    }
+    Ok(Command::Submit {
+        year: number(year, "year")?,
+        day: day_number(day)?,
+        part,
+        input,
+        answer,
+        url: args.values.get("--url").cloned(),
+    })
+}
+
+fn parse_watch(args: Args) -> Result<Command, String> {
+    let ([year, day], false) = (args.positional.as_slice(), args.all) else {
+        return Err("watch expects a year and a day".into());
+    };
+    let part = args.values.get("--part").map_or(Ok(1), |part| part_number(part))?;
+    let fps = args.value("--fps", 10)?;
+    if fps == 0 {
+        return Err("--fps needs at least one frame a second".into());
+    }
+    let record = match args.values.get("--record").map(PathBuf::from) {
+        Some(path) => {
+            if fps > MAX_GIF_FPS {
+                return Err(format!("a GIF can't show more than {} frames a second", MAX_GIF_FPS));
+            }
+            let defaults = Recording::default();
+            let recording =
+                Recording { fps, scale: args.value("--scale", defaults.scale)?, every: args.value("--every", defaults.every)? };
+            if recording.scale == 0 || recording.every == 0 {
+                return Err("--scale and --every need to be at least 1".into());
+            }
+            Some((path, recording))
+        }
+        None if args.values.contains_key("--scale") || args.values.contains_key("--every") => {
+            return Err("--scale and --every only apply with --record".into());
+        }
+        None => None,
+    };
+    Ok(Command::Watch {
+        year: number(year, "year")?,
+        day: day_number(day)?,
+        part,
+        fps,
+        input: args.values.get("--input").cloned(),
+        record,
+    })
+}
+
+fn parse_gen(args: Args) -> Result<Command, String> {
+    let ([year, day], false) = (args.positional.as_slice(), args.all) else {
+        return Err("gen expects a year and a day".into());
+    };
+    let seed = args.values.get("--seed").map(|seed| number(seed, "--seed")).transpose()?;
+    let size = args.values.get("--size").map(|size| number(size, "--size")).transpose()?;
+    if size == Some(0) {
+        return Err("--size needs to be at least 1".into());
+    }
+    Ok(Command::Gen { year: number(year, "year")?, day: day_number(day)?, seed, size })
+}
+
+// The arguments after the command: positionals, --all, other `--switch`es
+// and `--name value` options.
+struct Args {
+    positional: Vec<String>,
+    all: bool,
+    switches: HashSet<String>,
+    values: HashMap<String, String>,
+}
+
+fn scan(mut args: impl Iterator<Item = String>, switches: &[&str], valued: &[&str]) -> Result<Args, String> {
+    let mut scanned = Args { positional: vec![], all: false, switches: HashSet::new(), values: HashMap::new() };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => scanned.all = true,
+            name if switches.contains(&name) => {
+                scanned.switches.insert(arg);
+            }
+            name if valued.contains(&name) => {
+                let value = args.next().ok_or(format!("{} expects a value", name))?;
+                scanned.values.insert(arg, value);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {:?}", arg)),
+            _ => scanned.positional.push(arg),
+        }
+    }
+    Ok(scanned)
+}
+
+impl Args {
+    fn selection(&self) -> Result<Selection, String> {
+        match (self.positional.as_slice(), self.all) {
+            ([], true) => Ok(Selection { year: None, day: None }),
+            ([year], true) => Ok(Selection { year: Some(number(year, "year")?), day: None }),
+            ([year, day], false) => Ok(Selection {
+                year: Some(number(year, "year")?),
+                day: Some(number(day, "day")?),
+            }),
+            ([_], false) => Err("expected a day or --all".into()),
+            ([], false) => Err("expected a year".into()),
+            _ => Err("too many arguments".into()),
+        }
+    }
+
+    fn value<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.values.get(name) {
+            Some(value) => number(value, name),
+            None => Ok(default),
+        }
+    }
+}
+
+fn day_number(arg: &str) -> Result<u8, String> {
+    let day = number(arg, "day")?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day {}: days run from 1 to 25", day));
+    }
+    Ok(day)
+}
+
+fn part_number(arg: &str) -> Result<u8, String> {
+    let part = number(arg, "part")?;
+    if !(1..=2).contains(&part) {
+        return Err(format!("invalid part {}: expected 1 or 2", part));
+    }
+    Ok(part)
+}
+
+fn number<T: FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    arg.parse().map_err(|_| format!("invalid {} {:?}", what, arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    fn run(year: Option<u16>, day: Option<u8>, input: Option<&str>) -> Command {
+        let input = input.map(String::from);
+        Command::Run { selection: Selection { year, day }, input, format: Format::Table, jobs: None, render: None, trace: vec![] }
+    }
+
+    #[test]
+    fn parse_run_single_day() {
+        assert_eq!(parse_str("run 2022 9"), Ok(run(Some(2022), Some(9), None)));
+    }
+
+    #[test]
+    fn parse_run_whole_year() {
+        assert_eq!(parse_str("run 2022 --all"), Ok(run(Some(2022), None, None)));
+        assert_eq!(parse_str("run --all 2015"), Ok(run(Some(2015), None, None)));
+    }
+
+    #[test]
+    fn parse_run_with_alternate_input() {
+        assert_eq!(parse_str("run 2022 9 --input other.txt"), Ok(run(Some(2022), Some(9), Some("other.txt"))));
+        assert_eq!(parse_str("run --input - 2022 9"), Ok(run(Some(2022), Some(9), Some("-"))));
+        assert!(parse_str("run 2022 --all --input other.txt").is_err());
+        assert!(parse_str("run 2022 9 --input").is_err());
+    }
+
+    #[test]
+    fn parse_run_with_format() {
+        let json = Command::Run { selection: Selection { year: None, day: None }, input: None, format: Format::Json, jobs: None, render: None, trace: vec![] };
+        assert_eq!(parse_str("run --all --format json"), Ok(json));
+        assert!(matches!(parse_str("run --all --format csv"), Ok(Command::Run { format: Format::Csv, .. })));
+        assert!(parse_str("run --all --format xml").is_err());
+    }
+
+    #[test]
+    fn parse_run_with_jobs() {
+        assert!(matches!(parse_str("run --all --jobs 3"), Ok(Command::Run { jobs: Some(3), .. })));
+        assert!(parse_str("run --all --jobs 0").is_err());
+        assert!(parse_str("run --all --jobs many").is_err());
+    }
+
+    #[test]
+    fn parse_run_with_render() {
+        assert!(matches!(parse_str("run 2022 8 --render out.png"), Ok(Command::Run { render: Some(path), .. }) if path == Path::new("out.png")));
+        assert!(parse_str("run 2022 8 --render out.ppm").is_ok());
+        assert!(parse_str("run 2022 8 --render out.jpg").is_err());
+        assert!(parse_str("run 2022 --all --render out.png").is_err());
+        assert_eq!(part_path(Path::new("pics/out.png"), 2), Path::new("pics/out-2.png"));
+    }
+
+    #[test]
+    fn parse_run_with_trace() {
+        let Ok(Command::Run { trace, .. }) = parse_str("run 2022 7 --trace day7=debug,day5=trace") else { panic!() };
+        assert_eq!(trace.len(), 2);
+        assert!(parse_str("run 2022 7 --trace day7=loud").is_err());
+    }
+
+    #[test]
+    fn parse_run_everything() {
+        assert_eq!(parse_str("run --all"), Ok(run(None, None, None)));
+    }
+
+    #[test]
+    fn parse_bench_with_defaults() {
+        assert_eq!(
+            parse_str("bench 2022 8"),
+            Ok(Command::Bench {
+                selection: Selection { year: Some(2022), day: Some(8) },
+                options: Options::default(),
+                save: None,
+                baseline: None,
+                threshold: 10.0,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_bench_with_options() {
+        assert_eq!(
+            parse_str("bench --all --samples 50 --warmup 0 --baseline base.txt --threshold 5"),
+            Ok(Command::Bench {
+                selection: Selection { year: None, day: None },
+                options: Options { warmup: 0, samples: 50 },
+                save: None,
+                baseline: Some("base.txt".into()),
+                threshold: 5.0,
+            })
+        );
+        assert!(parse_str("bench --all --samples many").is_err());
+        assert!(parse_str("bench --all --samples 0").is_err());
+        assert!(parse_str("bench --all --input x").is_err());
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert_eq!(
+            parse_str("verify --all"),
+            Ok(Command::Verify { selection: Selection { year: None, day: None }, answers: None, record: false })
+        );
+        assert_eq!(
+            parse_str("verify 2022 7 --record --answers mine.txt"),
+            Ok(Command::Verify {
+                selection: Selection { year: Some(2022), day: Some(7) },
+                answers: Some("mine.txt".into()),
+                record: true,
+            })
+        );
+        assert!(parse_str("run 2022 7 --record").is_err());
+    }
+
+    #[test]
+    fn parse_profile() {
+        assert_eq!(
+            parse_str("profile 2022 5"),
+            Ok(Command::Profile { selection: Selection { year: Some(2022), day: Some(5) } })
+        );
+        assert!(parse_str("profile 2022 5 --input x").is_err());
+    }
+
+    #[test]
+    fn parse_new() {
+        assert_eq!(parse_str("new 2022 11"), Ok(Command::New { year: 2022, day: 11 }));
+        assert!(parse_str("new 2022").is_err());
+        assert!(parse_str("new 2022 --all").is_err());
+        assert!(parse_str("new 2022 26").is_err());
+        assert!(parse_str("new 2022 0").is_err());
+    }
+
+    #[test]
+    fn parse_fetch() {
+        assert_eq!(
+            parse_str("fetch 2022 --all"),
+            Ok(Command::Fetch { selection: Selection { year: Some(2022), day: None }, url: None })
+        );
+        assert_eq!(
+            parse_str("fetch 2022 9 --url http://localhost:8080"),
+            Ok(Command::Fetch {
+                selection: Selection { year: Some(2022), day: Some(9) },
+                url: Some("http://localhost:8080".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_submit() {
+        assert_eq!(
+            parse_str("submit 2022 5 2"),
+            Ok(Command::Submit { year: 2022, day: 5, part: 2, input: None, answer: None, url: None })
+        );
+        assert_eq!(
+            parse_str("submit 2022 10 2 --answer PLGFKAZG --url http://localhost:8080"),
+            Ok(Command::Submit {
+                year: 2022,
+                day: 10,
+                part: 2,
+                input: None,
+                answer: Some("PLGFKAZG".into()),
+                url: Some("http://localhost:8080".into()),
+            })
+        );
+        assert!(parse_str("submit 2022 5").is_err());
+        assert!(parse_str("submit 2022 5 3").is_err());
+        assert!(parse_str("submit 2022 --all 1").is_err());
+        assert!(parse_str("submit 2022 5 1 --answer X --input other.txt").is_err());
+    }
+
+    #[test]
+    fn parse_watch() {
+        assert_eq!(
+            parse_str("watch 2022 9"),
+            Ok(Command::Watch { year: 2022, day: 9, part: 1, fps: 10, input: None, record: None })
+        );
+        assert_eq!(
+            parse_str("watch 2022 5 --part 2 --fps 60 --input small.txt"),
+            Ok(Command::Watch { year: 2022, day: 5, part: 2, fps: 60, input: Some("small.txt".into()), record: None })
+        );
+        assert!(parse_str("watch 2022 --all").is_err());
+        assert!(parse_str("watch 2022 9 --part 3").is_err());
+        assert!(parse_str("watch 2022 9 --fps 0").is_err());
+    }
+
+    #[test]
+    fn parse_watch_with_record() {
+        let recording = Recording { fps: 25, scale: 2, every: 5 };
+        assert_eq!(
+            parse_str("watch 2022 9 --record rope.gif --fps 25 --scale 2 --every 5"),
+            Ok(Command::Watch { year: 2022, day: 9, part: 1, fps: 25, input: None, record: Some(("rope.gif".into(), recording)) })
+        );
+        assert!(matches!(parse_str("watch 2022 9 --record rope.gif"), Ok(Command::Watch { record: Some((_, Recording { fps: 10, scale: 4, every: 1 })), .. })));
+        assert!(parse_str("watch 2022 9 --record rope.gif --fps 60").is_err());
+        assert!(parse_str("watch 2022 9 --record rope.gif --every 0").is_err());
+        assert!(parse_str("watch 2022 9 --scale 2").is_err());
+    }
+
+    #[test]
+    fn parse_gen() {
+        assert_eq!(parse_str("gen 2022 9"), Ok(Command::Gen { year: 2022, day: 9, seed: None, size: None }));
+        assert_eq!(
+            parse_str("gen 2022 8 --seed 42 --size 10"),
+            Ok(Command::Gen { year: 2022, day: 8, seed: Some(42), size: Some(10) })
+        );
+        assert!(parse_str("gen 2022 --all").is_err());
+        assert!(parse_str("gen 2022 8 --seed -1").is_err());
+        assert!(parse_str("gen 2022 8 --size 0").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_bad_arguments() {
+        assert!(parse_str("run 2022").is_err());
+        assert!(parse_str("run 2022 nine").is_err());
+        assert!(parse_str("run 2022 9 --all").is_err());
+        assert!(parse_str("run 2022 9 --fast").is_err());
+        assert!(parse_str("fly").is_err());
+    }
+
+    #[test]
+    fn parse_defaults_to_help() {
+        assert_eq!(parse_str(""), Ok(Command::Help));
+    }
+}
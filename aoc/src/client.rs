@@ -0,0 +1,165 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use ureq::http::Response;
+use ureq::{Agent, Body};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// Where the session token is kept when AOC_SESSION is not set, relative to
+// this crate. The file is ignored by git.
+pub const SESSION_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../.session");
+
+// A client for the Advent of Code website, logged in with the value of the
+// `session` cookie from a browser.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(concat!("aoc-runner/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .into();
+        Self { agent, base_url: base_url.trim_end_matches('/').into(), session: session.trim().into() }
+    }
+
+    // Reads the session token from AOC_SESSION, or else from `path`.
+    pub fn session(path: &Path) -> Result<String, String> {
+        if let Ok(session) = env::var("AOC_SESSION") {
+            return Ok(session);
+        }
+        fs::read_to_string(path).map_err(|err| {
+            format!("no session token: set AOC_SESSION or save it to {} ({})", path.display(), err)
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = self.url(&format!("/{}/day/{}/input", year, day));
+        body(&url, self.agent.get(&url).header("Cookie", &self.cookie()).call())
+    }
+
+    // Posts `answer` for one part of a day, returning the page that says
+    // whether it was right.
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        let url = self.url(&format!("/{}/day/{}/answer", year, day));
+        let form = [("level", part.to_string()), ("answer", answer.to_string())];
+        body(&url, self.agent.post(&url).header("Cookie", &self.cookie()).send_form(form))
+    }
+}
+
+// The body of a successful response.
+fn body(url: &str, response: Result<Response<Body>, ureq::Error>) -> Result<String, String> {
+    let mut response = response.map_err(|err| format!("could not fetch {}: {}", url, err))?;
+    let body = response.body_mut().read_to_string().map_err(|err| format!("could not read {}: {}", url, err))?;
+    match response.status().as_u16() {
+        200 => Ok(body),
+        status => Err(format!("{} answered {}: {}", url, status, body.lines().next().unwrap_or(""))),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+// Downloads the input for `year`/`day` into `path`, unless the file is
+// already there. An empty file (as left by `aoc new`) does not count.
+pub fn fetch_input(client: &Client, path: &Path, year: u16, day: u8) -> Result<Fetched, String> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached);
+    }
+    let input = client.input(year, day)?;
+    fs::write(path, input).map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("aoc_client_{}_{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn input_sends_the_session_cookie() {
+        let (url, server) = mock::serve(vec![(200, "1\n2\n")]);
+        let input = Client::new(&url, "abc123\n").input(2022, 9);
+        let requests = server.join().unwrap();
+        assert_eq!(input, Ok("1\n2\n".into()));
+        assert_eq!(requests[0].line, "GET /2022/day/9/input HTTP/1.1");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+    }
+
+    #[test]
+    fn input_reports_error_statuses() {
+        let (url, server) = mock::serve(vec![(400, "Puzzle inputs differ by user.  Please log in.\n")]);
+        let err = Client::new(&url, "expired").input(2022, 9).unwrap_err();
+        server.join().unwrap();
+        assert!(err.ends_with("/2022/day/9/input answered 400: Puzzle inputs differ by user.  Please log in."));
+    }
+
+    #[test]
+    fn submit_posts_the_answer() {
+        let (url, server) = mock::serve(vec![(200, "<article><p>That's the right answer!</p></article>")]);
+        let page = Client::new(&url, "abc123").submit(2022, 5, 1, "CMZ XY");
+        let requests = server.join().unwrap();
+        assert_eq!(page, Ok("<article><p>That's the right answer!</p></article>".into()));
+        assert_eq!(requests[0].line, "POST /2022/day/5/answer HTTP/1.1");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(requests[0].body, "level=1&answer=CMZ+XY");
+    }
+
+    #[test]
+    fn fetch_input_downloads_once() {
+        let path = fixture("cached");
+        let (url, server) = mock::serve(vec![(200, "R 4\n")]);
+        let client = Client::new(&url, "abc123");
+        assert_eq!(fetch_input(&client, &path, 2022, 9), Ok(Fetched::Downloaded));
+        assert_eq!(server.join().unwrap().len(), 1);
+        // the server has gone, so this would fail if it asked again
+        assert_eq!(fetch_input(&client, &path, 2022, 9), Ok(Fetched::Cached));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "R 4\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn fetch_input_replaces_empty_placeholders() {
+        let path = fixture("placeholder");
+        fs::write(&path, "").unwrap();
+        let (url, server) = mock::serve(vec![(200, "noop\n")]);
+        assert_eq!(fetch_input(&Client::new(&url, "abc123"), &path, 2022, 10), Ok(Fetched::Downloaded));
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "noop\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn fetch_input_keeps_the_cache_on_errors() {
+        let path = fixture("missing");
+        let (url, server) = mock::serve(vec![(404, "Not Found")]);
+        assert!(fetch_input(&Client::new(&url, "abc123"), &path, 2022, 25).is_err());
+        server.join().unwrap();
+        assert!(!path.exists());
+    }
+}
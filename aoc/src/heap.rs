@@ -0,0 +1,210 @@
+// Heap profiling: a global allocator that counts what each thread allocates.
+// It is only installed with the `alloc-profile` feature (and in tests), so
+// normal runs and benchmarks pay nothing for it.
+use std::cell::Cell;
+use std::fmt::Write;
+
+use aoc_common::{ParseError, Solution};
+
+// Counted per thread, so steps running on other threads don't show up in a
+// measurement. Plain `Cell`s have no destructor, so they stay usable while a
+// thread is being torn down.
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+#[cfg(any(test, feature = "alloc-profile"))]
+pub use allocator::Counting;
+
+#[cfg(any(test, feature = "alloc-profile"))]
+mod allocator {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::{ALLOCATIONS, BYTES, LIVE, PEAK};
+
+    pub struct Counting;
+
+    fn allocated(size: usize) {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size));
+        resized(size as isize);
+    }
+
+    fn resized(change: isize) {
+        let Ok(live) = LIVE.try_with(|live| {
+            live.set(live.get() + change);
+            live.get()
+        }) else {
+            return;
+        };
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live)));
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            resized(-(layout.size() as isize));
+        }
+
+        // Counted as a fresh allocation of the new size that frees the old one.
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                resized(-(layout.size() as isize));
+                allocated(new_size);
+            }
+            new
+        }
+    }
+}
+
+// Whether the counting allocator is installed in this build.
+pub const ENABLED: bool = cfg!(any(test, feature = "alloc-profile"));
+
+// What one step allocated: how many allocations, how many bytes in total, and
+// the most it held at once on top of what was live when it started.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+// Runs `f` on this thread, counting its allocations.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Usage) {
+    let (allocations, bytes, live) = (ALLOCATIONS.get(), BYTES.get(), LIVE.get());
+    PEAK.set(live);
+    let result = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.get() - allocations,
+        bytes: BYTES.get() - bytes,
+        peak: (PEAK.get() - live).max(0) as usize,
+    };
+    (result, usage)
+}
+
+pub struct Profile {
+    pub parse: Usage,
+    pub part1: Usage,
+    pub part2: Usage,
+}
+
+// Parses `input` and answers both parts, counting each step's allocations.
+pub fn profile<S: Solution>(input: &str) -> Result<Profile, ParseError> {
+    let (solution, parse) = measure(|| S::parse(input));
+    let solution = solution?;
+    let (_, part1) = measure(|| solution.part1());
+    let (_, part2) = measure(|| solution.part2());
+    Ok(Profile { parse, part1, part2 })
+}
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub profile: Profile,
+}
+
+pub fn table(rows: &[Row]) -> String {
+    let mut out = String::new();
+    writeln!(out, "year  day  step   {:>9}  {:>10}  {:>10}", "allocs", "bytes", "peak").unwrap();
+    for row in rows.iter() {
+        let profile = &row.profile;
+        for (step, usage) in [("parse", profile.parse), ("1", profile.part1), ("2", profile.part2)] {
+            writeln!(
+                out,
+                "{:>4}  {:>3}  {:<5}  {:>9}  {:>10}  {:>10}",
+                row.year,
+                row.day,
+                step,
+                usage.allocations,
+                size(usage.bytes),
+                size(usage.peak)
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1048576 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1048576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn measure_counts_allocations_and_bytes() {
+        let (_, usage) = measure(|| {
+            let a = black_box(vec![0u8; 1000]);
+            let b = black_box(vec![0u8; 24]);
+            (a, b)
+        });
+        assert_eq!(usage, Usage { allocations: 2, bytes: 1024, peak: 1024 });
+    }
+
+    #[test]
+    fn measure_tracks_the_peak_not_the_total() {
+        let (_, usage) = measure(|| {
+            for _ in 0..10 {
+                drop(black_box(vec![0u8; 100]));
+            }
+        });
+        assert_eq!(usage, Usage { allocations: 10, bytes: 1000, peak: 100 });
+    }
+
+    #[test]
+    fn measure_counts_growth_as_reallocation() {
+        let (_, usage) = measure(|| {
+            let mut v: Vec<u8> = Vec::with_capacity(100);
+            v.reserve_exact(200);
+            black_box(v)
+        });
+        assert_eq!(usage, Usage { allocations: 2, bytes: 300, peak: 200 });
+    }
+
+    #[test]
+    fn size_picks_a_readable_unit() {
+        assert_eq!(size(512), "512 B");
+        assert_eq!(size(1536), "1.5 KiB");
+        assert_eq!(size(3 * 1048576), "3.0 MiB");
+    }
+
+    #[test]
+    fn table_lists_every_step() {
+        let usage = Usage { allocations: 3, bytes: 2048, peak: 100 };
+        let rows = [Row { year: 2022, day: 9, profile: Profile { parse: usage, part1: usage, part2: Usage::default() } }];
+        let expected = "\
+year  day  step      allocs       bytes        peak
+2022    9  parse          3     2.0 KiB       100 B
+2022    9  1              3     2.0 KiB       100 B
+2022    9  2              0         0 B         0 B
+";
+        assert_eq!(table(&rows), expected);
+    }
+}
@@ -0,0 +1,471 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+mod answers;
+mod bench;
+mod cli;
+mod client;
+mod heap;
+#[cfg(test)]
+mod mock;
+mod player;
+mod pool;
+mod registry;
+mod report;
+mod scaffold;
+mod submit;
+mod trace;
+
+use aoc_common::bench::Options;
+use aoc_common::image::{self, Recording};
+use aoc_common::Source;
+use cli::{Command, Selection};
+use registry::Day;
+use report::Row;
+
+#[cfg(any(test, feature = "alloc-profile"))]
+#[global_allocator]
+static ALLOCATOR: heap::Counting = heap::Counting;
+
+// Reads a day's input, reporting any failure against the day.
+fn read_input(day: &Day, input: Option<&str>) -> Option<(Source, String)> {
+    let source = Source::from_arg(input, day.input);
+    match source.read() {
+        Ok(text) => Some((source, text)),
+        Err(err) => {
+            eprintln!("{} day {}: could not read {}: {}", day.year, day.day, source, err);
+            None
+        }
+    }
+}
+
+fn select(selection: &Selection) -> Option<Vec<&'static Day>> {
+    let days = registry::select(selection.year, selection.day);
+    if days.is_empty() {
+        eprintln!("no solutions registered for that selection");
+        return None;
+    }
+    Some(days)
+}
+
+fn run(
+    selection: Selection,
+    input: Option<String>,
+    format: report::Format,
+    jobs: Option<usize>,
+    render: Option<&Path>,
+    directives: &[trace::Directive],
+) -> ExitCode {
+    let Some(days) = select(&selection) else { return ExitCode::FAILURE };
+    if !directives.is_empty() {
+        trace::init(directives, registry::DAYS);
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    let mut inputs = vec![];
+    for day in days {
+        match read_input(day, input.as_deref()) {
+            Some((source, input)) => inputs.push((day, source, input)),
+            None => status = ExitCode::FAILURE,
+        }
+    }
+
+    let workers = jobs.unwrap_or_else(pool::default_workers).min(inputs.len()).max(1);
+    let start = Instant::now();
+    let results = pool::map(&inputs, workers, |(day, _, input)| (day.solve)(input));
+    let summary = report::Summary { workers, wall: start.elapsed() };
+
+    let mut rows = vec![];
+    for ((day, source, input), result) in inputs.iter().zip(results) {
+        match result {
+            Ok(answers) => rows.push(Row { year: day.year, day: day.day, input: source.to_string(), answers }),
+            Err(err) => {
+                eprint!("{} day {}: {}", day.year, day.day, err.diagnostic(input, &source.to_string()));
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    if !rows.is_empty() || format != report::Format::Table {
+        print!("{}", report::render(&rows, &summary, format));
+    }
+    if let (Some(path), [(day, _, input)]) = (render, inputs.as_slice()) {
+        if !draw(day, input, path) {
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    status
+}
+
+// Saves a picture of each of a day's parts next to `path`.
+fn draw(day: &Day, input: &str, path: &Path) -> bool {
+    let Some(picture) = day.picture else {
+        eprintln!("{} day {} has no pictures to render", day.year, day.day);
+        return false;
+    };
+    for part in 1..=2 {
+        let path = cli::part_path(path, part);
+        // solving has already reported any parse error
+        let Ok(drawn) = picture(input, part) else { return false };
+        match image::save(&drawn, &path) {
+            Ok(()) => eprintln!("rendered part {} to {}", part, path.display()),
+            Err(err) => {
+                eprintln!("could not render to {}: {}", path.display(), err);
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn run_bench(
+    selection: Selection,
+    options: Options,
+    save: Option<&Path>,
+    baseline: Option<&Path>,
+    threshold: f64,
+) -> ExitCode {
+    let Some(days) = select(&selection) else { return ExitCode::FAILURE };
+    let baseline = match baseline.map(bench::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("could not load baseline: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    let mut rows = vec![];
+    for day in days {
+        let Some((source, input)) = read_input(day, None) else {
+            status = ExitCode::FAILURE;
+            continue;
+        };
+        match (day.bench)(&input, &options) {
+            Ok(result) => rows.push(bench::Row { year: day.year, day: day.day, bench: result }),
+            Err(err) => {
+                eprint!("{} day {}: {}", day.year, day.day, err.diagnostic(&input, &source.to_string()));
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let (table, regressions) = bench::table(&rows, baseline.as_ref(), threshold);
+    print!("{}", table);
+    if regressions > 0 {
+        eprintln!("{} step(s) slower than the baseline by more than {}%", regressions, threshold);
+        status = ExitCode::FAILURE;
+    }
+    if let Some(path) = save {
+        if let Err(err) = bench::save(path, &rows) {
+            eprintln!("could not save baseline to {}: {}", path.display(), err);
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    status
+}
+
+fn profile(selection: Selection) -> ExitCode {
+    if !heap::ENABLED {
+        eprintln!("allocation counting is not built in; run with `cargo run --features alloc-profile -p aoc`");
+        return ExitCode::FAILURE;
+    }
+    let Some(days) = select(&selection) else { return ExitCode::FAILURE };
+
+    let mut status = ExitCode::SUCCESS;
+    let mut rows = vec![];
+    for day in days {
+        match (day.profile)(day.input) {
+            Ok(profile) => rows.push(heap::Row { year: day.year, day: day.day, profile }),
+            Err(err) => {
+                eprint!("{} day {}: {}", day.year, day.day, err.diagnostic(day.input, "<embedded input>"));
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    print!("{}", heap::table(&rows));
+
+    status
+}
+
+fn verify(selection: Selection, path: Option<&Path>, record: bool) -> ExitCode {
+    let Some(days) = select(&selection) else { return ExitCode::FAILURE };
+    let path = path.unwrap_or(Path::new(answers::PATH));
+    let mut known = match answers::load(path) {
+        Ok(known) => known,
+        Err(err) => {
+            eprintln!("could not load known answers: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    let mut recorded = 0;
+    for day in days {
+        let answers = match (day.solve)(day.input) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprint!("{} day {}: {}", day.year, day.day, err.diagnostic(day.input, "<embedded input>"));
+                failures += 1;
+                continue;
+            }
+        };
+        for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+            let key = (day.year, day.day, part);
+            let label = format!("{:>4}  {:>3}  {:>4}", day.year, day.day, part);
+            match answers::check(&known, key, &answer.value) {
+                answers::Verdict::Correct => println!("{}  ok", label),
+                answers::Verdict::Wrong { expected } => {
+                    println!("{}  WRONG\n  expected: {}\n  got:      {}", label, expected, answer.value);
+                    failures += 1;
+                }
+                answers::Verdict::Unknown if record => {
+                    known.insert(key, answer.value.clone());
+                    recorded += 1;
+                    println!("{}  recorded", label);
+                }
+                answers::Verdict::Unknown => println!("{}  unknown (no known answer)", label),
+            }
+        }
+    }
+
+    if recorded > 0 {
+        if let Err(err) = answers::save(path, &known) {
+            eprintln!("could not save known answers to {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+    if failures > 0 {
+        eprintln!("{} answer(s) differ from the known answers", failures);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn new_day(year: u16, day: u8) -> ExitCode {
+    match scaffold::new_day(Path::new(scaffold::ROOT), year, day) {
+        Ok(dir) => {
+            println!("created {}", dir.display());
+            println!("next: paste the puzzle into {0}/README.md and your input into {0}/input", dir.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("could not create {} day {}: {}", year, day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(selection: Selection, url: Option<&str>) -> ExitCode {
+    let Some(days) = select(&selection) else { return ExitCode::FAILURE };
+    let session = match client::Client::session(Path::new(client::SESSION_PATH)) {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = client::Client::new(url.unwrap_or(client::DEFAULT_URL), &session);
+
+    let mut status = ExitCode::SUCCESS;
+    for day in days {
+        let path = Path::new(scaffold::ROOT).join(scaffold::day_dir(day.year, day.day)).join("input");
+        let label = format!("{:>4}  {:>3}", day.year, day.day);
+        match client::fetch_input(&client, &path, day.year, day.day) {
+            Ok(client::Fetched::Cached) => println!("{}  cached", label),
+            Ok(client::Fetched::Downloaded) => println!("{}  downloaded", label),
+            Err(err) => {
+                println!("{}  failed: {}", label, err);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+// Solves one part of a day and submits the answer, unless it is known to be
+// wrong, or already known to be right.
+fn submit(year: u16, day: u8, part: u8, input: Option<&str>, answer: Option<String>, url: Option<&str>) -> ExitCode {
+    let Some(days) = select(&Selection { year: Some(year), day: Some(day) }) else { return ExitCode::FAILURE };
+    let registered = days[0];
+    let key = (year, day, part);
+    let solved = answer.is_none();
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let Some((source, input)) = read_input(registered, input) else { return ExitCode::FAILURE };
+            match (registered.solve)(&input) {
+                Ok(answers) if part == 1 => answers.part1.value,
+                Ok(answers) => answers.part2.value,
+                Err(err) => {
+                    eprint!("{} day {}: {}", year, day, err.diagnostic(&input, &source.to_string()));
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    };
+    if answer.contains('\n') {
+        eprintln!("the answer spans several lines; read it off and pass it with --answer:\n{}", answer);
+        return ExitCode::FAILURE;
+    }
+
+    let answers_path = Path::new(answers::PATH);
+    let (mut known, mut memory) = match (answers::load(answers_path), submit::load(Path::new(submit::PATH))) {
+        (Ok(known), Ok(memory)) => (known, memory),
+        (Err(err), _) | (_, Err(err)) => {
+            eprintln!("could not load earlier answers: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(accepted) = known.get(&key) {
+        println!("{} day {} part {} is already solved: {}", year, day, part, accepted.replace('\n', "\n  "));
+        return ExitCode::SUCCESS;
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+    if let Some(reason) = memory.refuse(key, &answer, now) {
+        eprintln!("not submitting {}: {}", answer, reason);
+        return ExitCode::FAILURE;
+    }
+
+    let session = match client::Client::session(Path::new(client::SESSION_PATH)) {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = client::Client::new(url.unwrap_or(client::DEFAULT_URL), &session);
+    let page = match client.submit(year, day, part, &answer) {
+        Ok(page) => page,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut status = ExitCode::FAILURE;
+    match submit::parse_response(&page) {
+        submit::Verdict::Correct => {
+            println!("{} is correct", answer);
+            // a typed-in answer (say, the letters on day 10's screen) isn't
+            // what the runner prints, so `verify` couldn't check it
+            if solved {
+                known.insert(key, answer);
+                if let Err(err) = answers::save(answers_path, &known) {
+                    eprintln!("could not save known answers to {}: {}", answers_path.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            }
+            status = ExitCode::SUCCESS;
+        }
+        submit::Verdict::Wrong { hint, wait } => {
+            let why = match hint {
+                submit::Hint::TooHigh => " (too high)",
+                submit::Hint::TooLow => " (too low)",
+                submit::Hint::None => "",
+            };
+            println!("{} is wrong{}", answer, why);
+            memory.reject(key, &answer, hint);
+            memory.wait(now, wait);
+        }
+        submit::Verdict::RateLimited { wait } => {
+            println!("answered too recently; wait {}s before trying again", wait.map_or(0, |wait| wait.as_secs()));
+            memory.wait(now, wait);
+        }
+        submit::Verdict::WrongLevel => println!("that part is already solved, or part 1 isn't yet"),
+        submit::Verdict::Unknown(text) => println!("unexpected response: {}", text),
+    }
+    if let Err(err) = submit::save(Path::new(submit::PATH), &memory) {
+        eprintln!("could not save submissions to {}: {}", submit::PATH, err);
+        return ExitCode::FAILURE;
+    }
+    status
+}
+
+// Plays one part of a day's simulation in the terminal, or records it.
+fn watch(year: u16, day: u8, part: u8, fps: u32, input: Option<&str>, record: Option<(PathBuf, Recording)>) -> ExitCode {
+    let Some(days) = select(&Selection { year: Some(year), day: Some(day) }) else { return ExitCode::FAILURE };
+    let registered = days[0];
+    let Some(frames) = registered.frames else {
+        eprintln!("{} day {} has no simulation to watch", year, day);
+        return ExitCode::FAILURE;
+    };
+    let Some((source, input)) = read_input(registered, input) else { return ExitCode::FAILURE };
+    let frames = match frames(&input, part) {
+        Ok(frames) => frames,
+        Err(err) => {
+            eprint!("{} day {}: {}", year, day, err.diagnostic(&input, &source.to_string()));
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some((path, recording)) = record {
+        return match image::save_gif(frames, &recording, &path) {
+            Ok(kept) => {
+                println!("recorded {} frames to {}", kept, path.display());
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("could not record to {}: {}", path.display(), err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+    match player::play(frames, player::Controls::new(fps)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("could not play {} day {}: {}", year, day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Prints a random input for a day, from `seed` or, without one, a seed that
+// is shown on stderr so the input can be made again.
+fn gen(year: u16, day: u8, seed: Option<u64>, size: Option<usize>) -> ExitCode {
+    let Some(days) = select(&Selection { year: Some(year), day: Some(day) }) else { return ExitCode::FAILURE };
+    let Some(generate) = days[0].generate else {
+        eprintln!("{} day {} has no input generator", year, day);
+        return ExitCode::FAILURE;
+    };
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_nanos() as u64);
+        eprintln!("seed {}", seed);
+        seed
+    });
+    print!("{}", generate(seed, size));
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    match cli::parse(env::args().skip(1)) {
+        Ok(Command::Run { selection, input, format, jobs, render, trace }) => {
+            run(selection, input, format, jobs, render.as_deref(), &trace)
+        }
+        Ok(Command::Bench { selection, options, save, baseline, threshold }) => {
+            run_bench(selection, options, save.as_deref(), baseline.as_deref(), threshold)
+        }
+        Ok(Command::Verify { selection, answers, record }) => verify(selection, answers.as_deref(), record),
+        Ok(Command::Profile { selection }) => profile(selection),
+        Ok(Command::New { year, day }) => new_day(year, day),
+        Ok(Command::Fetch { selection, url }) => fetch(selection, url.as_deref()),
+        Ok(Command::Submit { year, day, part, input, answer, url }) => {
+            submit(year, day, part, input.as_deref(), answer, url.as_deref())
+        }
+        Ok(Command::Watch { year, day, part, fps, input, record }) => {
+            watch(year, day, part, fps, input.as_deref(), record)
+        }
+        Ok(Command::Gen { year, day, seed, size }) => gen(year, day, seed, size),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("aoc: {}\n{}", message, cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
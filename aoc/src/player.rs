@@ -0,0 +1,245 @@
+// Plays a simulation's frames in the terminal for `aoc watch`. The terminal
+// is only touched by `play`; the controls and the layout of each screen are
+// worked out separately so they can be tested.
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use aoc_common::animation::{Frame, Frames};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+pub const MAX_FPS: u32 = 1000;
+
+const HELP: &str = "space pause/play  \u{2192} or n step  + - speed  q quit";
+
+// What a key press asks of the player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Wake {
+    // show the next frame
+    Advance,
+    // redraw the current frame, e.g. to show a new speed
+    Redraw,
+    Quit,
+    Ignore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Controls {
+    pub paused: bool,
+    pub fps: u32,
+}
+
+impl Controls {
+    pub fn new(fps: u32) -> Self {
+        Self { paused: false, fps: fps.clamp(1, MAX_FPS) }
+    }
+
+    fn press(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Wake {
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Wake::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Wake::Quit,
+            KeyCode::Char(' ') | KeyCode::Char('p') => {
+                self.paused = !self.paused;
+                Wake::Redraw
+            }
+            // stepping pauses, so the next frame waits for the next step
+            KeyCode::Right | KeyCode::Char('n') | KeyCode::Char('.') => {
+                self.paused = true;
+                Wake::Advance
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.fps = (self.fps * 2).min(MAX_FPS);
+                Wake::Redraw
+            }
+            KeyCode::Char('-') => {
+                self.fps = (self.fps / 2).max(1);
+                Wake::Redraw
+            }
+            _ => Wake::Ignore,
+        }
+    }
+
+    // How long to show a frame for, or `None` to show it until a key says
+    // otherwise.
+    fn delay(&self) -> Option<Duration> {
+        (!self.paused).then(|| Duration::from_secs(1) / self.fps)
+    }
+}
+
+// The top left cell of the part of the grid in view.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Viewport {
+    x: usize,
+    y: usize,
+}
+
+impl Viewport {
+    // Scrolls so the frame's focus stays in view, only moving once it gets
+    // near an edge so the picture doesn't shift on every frame.
+    fn follow(&mut self, frame: &Frame, columns: usize, rows: usize) {
+        let focus = frame.focus.unzip();
+        self.x = scroll(self.x, frame.grid.width(), columns, focus.0);
+        self.y = scroll(self.y, frame.grid.height(), rows, focus.1);
+    }
+}
+
+// The first of `shown` out of `size` cells to show, starting from `start`,
+// keeping `focus` at least a quarter of the window from either edge.
+fn scroll(start: usize, size: usize, shown: usize, focus: Option<usize>) -> usize {
+    if size <= shown {
+        return 0;
+    }
+    let last = size - shown;
+    let Some(focus) = focus else { return start.min(last) };
+    let margin = shown / 4;
+    if focus < start + margin || focus >= (start + shown).saturating_sub(margin) {
+        focus.saturating_sub(shown / 2).min(last)
+    } else {
+        start.min(last)
+    }
+}
+
+// The lines of the screen for frame `number`: the caption, as much of the
+// grid as fits, and a status line, each cut to `width` characters.
+fn layout(frame: &Frame, number: usize, controls: &Controls, done: bool, viewport: &mut Viewport, (width, height): (usize, usize)) -> Vec<String> {
+    let rows = height.saturating_sub(2);
+    viewport.follow(frame, width, rows);
+    let mut lines = vec![cut(&frame.caption, width)];
+    for y in (viewport.y..frame.grid.height()).take(rows) {
+        lines.push(frame.grid.row(y).iter().skip(viewport.x).take(width).collect());
+    }
+    let state = match (done, controls.paused) {
+        (true, _) => "finished".to_string(),
+        (false, true) => "paused".to_string(),
+        (false, false) => format!("{} fps", controls.fps),
+    };
+    lines.push(cut(&format!("frame {}  {}  |  {}", number, state, HELP), width));
+    lines
+}
+
+fn cut(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+// Plays `frames` until they run out and `q` is pressed. The terminal is
+// restored however playing ends.
+pub fn play(frames: Frames, controls: Controls) -> io::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    let result = execute!(out, EnterAlternateScreen, Hide).and_then(|_| run(&mut out, frames, controls));
+    let restored = execute!(out, Show, LeaveAlternateScreen).and(terminal::disable_raw_mode());
+    result.and(restored)
+}
+
+fn run(out: &mut impl Write, mut frames: Frames, mut controls: Controls) -> io::Result<()> {
+    let Some(mut frame) = frames.next() else { return Ok(()) };
+    let mut number = 1;
+    let mut done = false;
+    let mut viewport = Viewport::default();
+    loop {
+        let (width, height) = terminal::size()?;
+        let lines = layout(&frame, number, &controls, done, &mut viewport, (width as usize, height as usize));
+        for (y, line) in lines.iter().enumerate() {
+            queue!(out, MoveTo(0, y as u16), Print(line), Clear(ClearType::UntilNewLine))?;
+        }
+        queue!(out, Clear(ClearType::FromCursorDown))?;
+        out.flush()?;
+
+        let wake = wait(&mut controls, done)?;
+        if wake == Wake::Quit {
+            return Ok(());
+        }
+        if wake == Wake::Advance && !done {
+            match frames.next() {
+                Some(next) => {
+                    frame = next;
+                    number += 1;
+                }
+                None => done = true,
+            }
+        }
+    }
+}
+
+// Waits for the current frame's time to be up, or for a key that changes
+// what is shown.
+fn wait(controls: &mut Controls, done: bool) -> io::Result<Wake> {
+    let deadline = controls.delay().filter(|_| !done).map(|delay| Instant::now() + delay);
+    loop {
+        let ready = match deadline {
+            Some(deadline) => event::poll(deadline.saturating_duration_since(Instant::now()))?,
+            None => true,
+        };
+        if !ready {
+            return Ok(Wake::Advance);
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => match controls.press(key.code, key.modifiers) {
+                Wake::Ignore => {}
+                wake => return Ok(wake),
+            },
+            Event::Resize(..) => return Ok(Wake::Redraw),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Grid;
+
+    fn press(controls: &mut Controls, code: KeyCode) -> Wake {
+        controls.press(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn controls_pause_step_and_change_speed() {
+        let mut controls = Controls::new(10);
+        assert_eq!(controls.delay(), Some(Duration::from_millis(100)));
+        assert_eq!(press(&mut controls, KeyCode::Char(' ')), Wake::Redraw);
+        assert_eq!(controls.delay(), None);
+        assert_eq!(press(&mut controls, KeyCode::Char(' ')), Wake::Redraw);
+        assert_eq!(press(&mut controls, KeyCode::Right), Wake::Advance);
+        assert!(controls.paused);
+        press(&mut controls, KeyCode::Char('+'));
+        assert_eq!(controls.fps, 20);
+        for _ in 0..10 {
+            press(&mut controls, KeyCode::Char('-'));
+        }
+        assert_eq!(controls.fps, 1);
+        assert_eq!(Controls::new(5000).fps, MAX_FPS);
+        assert_eq!(press(&mut controls, KeyCode::Char('x')), Wake::Ignore);
+        assert_eq!(press(&mut controls, KeyCode::Esc), Wake::Quit);
+        assert_eq!(controls.press(KeyCode::Char('c'), KeyModifiers::CONTROL), Wake::Quit);
+    }
+
+    #[test]
+    fn scroll_keeps_the_focus_in_view() {
+        assert_eq!(scroll(0, 5, 10, Some(4)), 0);
+        assert_eq!(scroll(0, 100, 20, Some(10)), 0);
+        assert_eq!(scroll(0, 100, 20, Some(16)), 6);
+        assert_eq!(scroll(6, 100, 20, Some(17)), 6);
+        assert_eq!(scroll(6, 100, 20, Some(99)), 80);
+        assert_eq!(scroll(80, 100, 20, Some(0)), 0);
+        assert_eq!(scroll(90, 100, 20, None), 80);
+    }
+
+    #[test]
+    fn layout_crops_the_grid_around_the_focus() {
+        let grid = Grid::from_fn(8, 6, |x, y| char::from_digit(((x + y) % 10) as u32, 10).unwrap());
+        let frame = Frame::new(grid, "a long caption").focus(7, 5);
+        let controls = Controls::new(4);
+        let mut viewport = Viewport::default();
+        let lines = layout(&frame, 3, &controls, false, &mut viewport, (6, 5));
+        assert_eq!(lines[..4], ["a long", "567890", "678901", "789012"]);
+        assert_eq!(lines[4], "frame ");
+        let lines = layout(&frame, 3, &controls, true, &mut viewport, (40, 10));
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[1], "01234567");
+        assert_eq!(lines[7], "frame 3  finished  |  space pause/play  ");
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// The number of workers to use when none is given: one per available core.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |workers| workers.get())
+}
+
+// Runs `task` on every item using up to `workers` threads, each taking the
+// next unstarted item as soon as it is free. The results come back in the
+// order of `items`, however the work was interleaved.
+pub fn map<T: Sync, R: Send>(items: &[T], workers: usize, task: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let done = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else { break };
+                let result = task(item);
+                done.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut done = done.into_inner().unwrap();
+    done.sort_by_key(|&(index, _)| index);
+    done.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[test]
+    fn map_keeps_the_order_of_items() {
+        let items: Vec<u64> = (0..20).collect();
+        // later items finish first
+        let results = map(&items, 4, |&n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(results, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn map_uses_up_to_the_given_number_of_workers() {
+        let items: Vec<u32> = (0..8).collect();
+        let threads = |workers| {
+            let ids = map(&items, workers, |_| {
+                thread::sleep(Duration::from_millis(5));
+                thread::current().id()
+            });
+            ids.into_iter().collect::<HashSet<_>>().len()
+        };
+        assert_eq!(threads(1), 1);
+        assert!((2..=4).contains(&threads(4)));
+        assert!(threads(100) <= items.len());
+    }
+
+    #[test]
+    fn map_handles_no_items() {
+        let items: Vec<u32> = vec![];
+        assert!(map(&items, 0, |&n| n).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+use aoc_common::animation::{self, Frames};
+use aoc_common::bench::{self, Bench, Options};
+use aoc_common::image::{self, Image};
+use aoc_common::random;
+use aoc_common::{solve, Answers, ParseError};
+
+use crate::heap::{self, Profile};
+
+// A solved day the runner knows how to dispatch to.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    // the day's embedded puzzle input
+    pub input: &'static str,
+    pub solve: fn(&str) -> Result<Answers, ParseError>,
+    pub bench: fn(&str, &Options) -> Result<Bench, ParseError>,
+    pub profile: fn(&str) -> Result<Profile, ParseError>,
+    // the day's frames for `aoc watch`, if it can be watched
+    pub frames: Option<Simulate>,
+    // the day's pictures for `aoc run --render`, if it draws any
+    pub picture: Option<Draw>,
+    // makes up an input from a seed and a size, if the day can
+    pub generate: Option<fn(u64, Option<usize>) -> String>,
+}
+
+// Parses an input and starts simulating one of its parts.
+pub type Simulate = fn(&str, u8) -> Result<Frames, ParseError>;
+
+// Parses an input and draws one of its parts.
+pub type Draw = fn(&str, u8) -> Result<Image, ParseError>;
+
+// `day!(year, day, krate::Solution)`, followed by `simulation` if the
+// solution is also a `Simulation`, `picture` if it is a `Picture` and
+// `generator` if it is a `Generator`.
+macro_rules! day {
+    ($year:literal, $day:literal, $krate:ident :: $solution:ident $(, $extra:ident)*) => {{
+        #[allow(unused_mut)]
+        let mut day = Day {
+            year: $year,
+            day: $day,
+            input: $krate::INPUT,
+            solve: solve::<$krate::$solution>,
+            bench: bench::bench::<$krate::$solution>,
+            profile: heap::profile::<$krate::$solution>,
+            frames: None,
+            picture: None,
+            generate: None,
+        };
+        $(day!(@$extra day, $krate::$solution);)*
+        day
+    }};
+    (@simulation $name:ident, $krate:ident :: $solution:ident) => {
+        $name.frames = Some(animation::frames::<$krate::$solution>)
+    };
+    (@picture $name:ident, $krate:ident :: $solution:ident) => {
+        $name.picture = Some(image::picture::<$krate::$solution>)
+    };
+    (@generator $name:ident, $krate:ident :: $solution:ident) => {
+        $name.generate = Some(random::generate::<$krate::$solution>)
+    };
+}
+
+// Every registered day, ordered by year then day.
+pub static DAYS: &[Day] = &[
+    day!(2015, 1, aoc_2015_day_1::Day1, generator),
+    day!(2022, 1, aoc_2022_day_1::Day1, generator),
+    day!(2022, 2, aoc_2022_day_2::Day2, generator),
+    day!(2022, 3, aoc_2022_day_3::Day3, generator),
+    day!(2022, 4, aoc_2022_day_4::Day4, generator),
+    day!(2022, 5, aoc_2022_day_5::Day5, simulation, generator),
+    day!(2022, 6, aoc_2022_day_6::Day6, generator),
+    day!(2022, 7, aoc_2022_day_7::Day7, generator),
+    day!(2022, 8, aoc_2022_day_8::Day8, picture, generator),
+    day!(2022, 9, aoc_2022_day_9::Day9, simulation, picture, generator),
+    day!(2022, 10, aoc_2022_day_10::Day10, simulation, picture, generator),
+];
+
+// The registered days matching `year` and `day`; `None` matches everything.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<&'static Day> {
+    DAYS.iter()
+        .filter(|d| year.is_none_or(|year| d.year == year))
+        .filter(|d| day.is_none_or(|day| d.day == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_unique_and_ordered() {
+        for pair in DAYS.windows(2) {
+            assert!((pair[0].year, pair[0].day) < (pair[1].year, pair[1].day));
+        }
+    }
+
+    #[test]
+    fn every_registered_day_embeds_its_input() {
+        for day in DAYS.iter() {
+            assert!(!day.input.is_empty(), "{} day {}", day.year, day.day);
+        }
+    }
+
+    #[test]
+    fn select_filters_by_year_and_day() {
+        assert_eq!(select(Some(2015), None).len(), 1);
+        assert_eq!(select(Some(2022), None).len(), 10);
+        assert_eq!(select(Some(2022), Some(9))[0].day, 9);
+        assert!(select(Some(2022), Some(25)).is_empty());
+        assert_eq!(select(None, None).len(), DAYS.len());
+    }
+
+    #[test]
+    fn simulated_days_can_be_watched() {
+        let watchable: Vec<u8> = select(Some(2022), None).iter().filter(|d| d.frames.is_some()).map(|d| d.day).collect();
+        assert_eq!(watchable, [5, 9, 10]);
+        let day = select(Some(2022), Some(9))[0];
+        assert!((day.frames.unwrap())(day.input, 1).unwrap().next().is_some());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        // days fresh from `aoc new` have no generator yet
+        for day in DAYS.iter() {
+            let Some(generate) = day.generate else { continue };
+            assert_eq!(generate(3, Some(10)), generate(3, Some(10)), "{} day {}", day.year, day.day);
+            assert!((day.solve)(&generate(3, None)).is_ok(), "{} day {}", day.year, day.day);
+        }
+    }
+
+    #[test]
+    fn drawn_days_have_pictures() {
+        let drawn: Vec<u8> = select(Some(2022), None).iter().filter(|d| d.picture.is_some()).map(|d| d.day).collect();
+        assert_eq!(drawn, [8, 9, 10]);
+        let day = select(Some(2022), Some(10))[0];
+        assert_eq!((day.picture.unwrap())(day.input, 2).unwrap().width(), 320);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_common::Answers;
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    // where the input came from, e.g. a path or "<embedded input>"
+    pub input: String,
+    pub answers: Answers,
+}
+
+// How long producing the rows took from start to finish, and on how many
+// workers; the time summed over every step can be far longer.
+pub struct Summary {
+    pub workers: usize,
+    pub wall: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+pub fn render(rows: &[Row], summary: &Summary, format: Format) -> String {
+    match format {
+        Format::Table => table(rows, summary),
+        Format::Json => json(rows, summary),
+        Format::Csv => csv(rows),
+    }
+}
+
+// Lays out every answer in a table, one line per part. Multi-line answers
+// (e.g. a rendered screen) continue underneath in the answer column.
+pub fn table(rows: &[Row], summary: &Summary) -> String {
+    let width = rows
+        .iter()
+        .flat_map(|row| [&row.answers.part1.value, &row.answers.part2.value])
+        .flat_map(|value| value.lines())
+        .map(|line| line.chars().count())
+        .chain(["answer".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    writeln!(out, "year  day  part  {:<width$}  {:>10}", "answer", "time").unwrap();
+    let mut total = Duration::ZERO;
+    for row in rows.iter() {
+        let parts = [(1, &row.answers.part1), (2, &row.answers.part2)];
+        for (part, answer) in parts {
+            let mut lines = answer.value.lines();
+            let first = lines.next().unwrap_or("");
+            let elapsed = format!("{:.2?}", answer.elapsed);
+            writeln!(out, "{:>4}  {:>3}  {:>4}  {:<width$}  {:>10}", row.year, row.day, part, first, elapsed)
+                .unwrap();
+            for line in lines {
+                writeln!(out, "{:>4}  {:>3}  {:>4}  {}", "", "", "", line).unwrap();
+            }
+            total += answer.elapsed;
+        }
+        total += row.answers.parse;
+    }
+    writeln!(out, "total (including parsing): {:.2?}", total).unwrap();
+    writeln!(out, "wall-clock: {:.2?} on {} worker(s)", summary.wall, summary.workers).unwrap();
+
+    out
+}
+
+// Every day's answers and timings (in nanoseconds) as a JSON document:
+//
+//     {"runner": "aoc 0.1.0", "workers": 4, "wall_ns": 9000,
+//      "total_ns": 30000, "days": [{"year": 2022, "day": 9,
+//      "input": "<embedded input>", "parse_ns": 1000, "parts": [
+//      {"part": 1, "answer": "6175", "time_ns": 2000}, ...]}, ...]}
+pub fn json(rows: &[Row], summary: &Summary) -> String {
+    let total: Duration = rows
+        .iter()
+        .map(|row| row.answers.parse + row.answers.part1.elapsed + row.answers.part2.elapsed)
+        .sum();
+    let mut out = format!(
+        "{{\n  \"runner\": {},\n  \"workers\": {},\n  \"wall_ns\": {},\n  \"total_ns\": {},\n  \"days\": [",
+        json_string(RUNNER),
+        summary.workers,
+        summary.wall.as_nanos(),
+        total.as_nanos()
+    );
+    for (index, row) in rows.iter().enumerate() {
+        let separator = if index == 0 { "" } else { "," };
+        write!(
+            out,
+            "{}\n    {{\"year\": {}, \"day\": {}, \"input\": {}, \"parse_ns\": {}, \"parts\": [",
+            separator,
+            row.year,
+            row.day,
+            json_string(&row.input),
+            row.answers.parse.as_nanos()
+        )
+        .unwrap();
+        for (part, answer) in [(1, &row.answers.part1), (2, &row.answers.part2)] {
+            let separator = if part == 1 { "" } else { "," };
+            write!(
+                out,
+                "{}\n      {{\"part\": {}, \"answer\": {}, \"time_ns\": {}}}",
+                separator,
+                part,
+                json_string(&answer.value),
+                answer.elapsed.as_nanos()
+            )
+            .unwrap();
+        }
+        out.push_str("\n    ]}");
+    }
+    out.push_str(if rows.is_empty() { "]\n}\n" } else { "\n  ]\n}\n" });
+    out
+}
+
+const RUNNER: &str = concat!("aoc ", env!("CARGO_PKG_VERSION"));
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// One record per part, with a header line. Fields are quoted as needed,
+// so multi-line answers stay in a single (quoted) field.
+pub fn csv(rows: &[Row]) -> String {
+    let mut out = String::from("year,day,part,answer,time_ns,parse_ns,input\n");
+    for row in rows.iter() {
+        for (part, answer) in [(1, &row.answers.part1), (2, &row.answers.part2)] {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                row.year,
+                row.day,
+                part,
+                csv_field(&answer.value),
+                answer.elapsed.as_nanos(),
+                row.answers.parse.as_nanos(),
+                csv_field(&row.input)
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Answer;
+
+    fn answer(value: &str, millis: u64) -> Answer {
+        Answer { value: value.into(), elapsed: Duration::from_millis(millis) }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![Row {
+            year: 2022,
+            day: 9,
+            input: "<embedded input>".into(),
+            answers: Answers {
+                parse: Duration::from_millis(1),
+                part1: answer("6175", 2),
+                part2: answer("ab\ncd", 3),
+            },
+        }]
+    }
+
+    const SUMMARY: Summary = Summary { workers: 2, wall: Duration::from_millis(4) };
+
+    #[test]
+    fn table_lines_up_answers_and_timings() {
+        let rows = rows();
+        let expected = "\
+year  day  part  answer        time
+2022    9     1  6175        2.00ms
+2022    9     2  ab          3.00ms
+                 cd
+total (including parsing): 6.00ms
+wall-clock: 4.00ms on 2 worker(s)
+";
+        assert_eq!(table(&rows, &SUMMARY), expected);
+    }
+
+    #[test]
+    fn json_lists_every_part() {
+        let expected = format!(
+            "\
+{{
+  \"runner\": \"{}\",
+  \"workers\": 2,
+  \"wall_ns\": 4000000,
+  \"total_ns\": 6000000,
+  \"days\": [
+    {{\"year\": 2022, \"day\": 9, \"input\": \"<embedded input>\", \"parse_ns\": 1000000, \"parts\": [
+      {{\"part\": 1, \"answer\": \"6175\", \"time_ns\": 2000000}},
+      {{\"part\": 2, \"answer\": \"ab\\ncd\", \"time_ns\": 3000000}}
+    ]}}
+  ]
+}}
+",
+            RUNNER
+        );
+        assert_eq!(json(&rows(), &SUMMARY), expected);
+        assert!(json(&[], &SUMMARY).ends_with("\"total_ns\": 0,\n  \"days\": []\n}\n"));
+    }
+
+    #[test]
+    fn json_string_escapes_specials() {
+        assert_eq!(json_string("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        let expected = "\
+year,day,part,answer,time_ns,parse_ns,input
+2022,9,1,6175,2000000,1000000,<embedded input>
+2022,9,2,\"ab
+cd\",3000000,1000000,<embedded input>
+";
+        assert_eq!(csv(&rows()), expected);
+        assert_eq!(csv_field("say \"hi\", twice"), "\"say \"\"hi\"\", twice\"");
+    }
+}
@@ -0,0 +1,223 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The repository root, relative to this crate.
+pub const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+// Where the day template lives, relative to the root. Its files may use the
+// placeholders {{year}} and {{day}}.
+const TEMPLATE: &str = "templates/rust";
+
+// A day's directory, relative to the root.
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(&year.to_string()).join(format!("day_{}", day))
+}
+
+// Creates `<year>/day_<day>` under `root` from the template and registers it
+// with the workspace and the runner. Returns the new day's directory,
+// relative to `root`.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<PathBuf, String> {
+    let relative = day_dir(year, day);
+    let dir = root.join(&relative);
+    if dir.exists() {
+        return Err(format!("{} already exists", relative.display()));
+    }
+
+    // Work out every edit before touching anything, so a day that is already
+    // registered leaves the tree as it was.
+    let manifest = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc/Cargo.toml");
+    let registry = root.join("aoc/src/registry.rs");
+    let edits = [
+        (&manifest, add_member(&read(&manifest)?, year, day)?),
+        (&runner_manifest, add_dependency(&read(&runner_manifest)?, year, day)?),
+        (&registry, add_to_registry(&read(&registry)?, year, day)?),
+    ];
+
+    copy_template(&root.join(TEMPLATE), &dir, year, day)?;
+    for (path, text) in edits {
+        write(path, &text)?;
+    }
+    Ok(relative)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    fs::write(path, text).map_err(|err| format!("could not write {}: {}", path.display(), err))
+}
+
+fn render(template: &str, year: u16, day: u8) -> String {
+    template.replace("{{year}}", &year.to_string()).replace("{{day}}", &day.to_string())
+}
+
+fn copy_template(from: &Path, to: &Path, year: u16, day: u8) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|err| format!("could not create {}: {}", to.display(), err))?;
+    let entries = fs::read_dir(from).map_err(|err| format!("could not read {}: {}", from.display(), err))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("could not read {}: {}", from.display(), err))?;
+        let (source, target) = (entry.path(), to.join(entry.file_name()));
+        if source.is_dir() {
+            if entry.file_name() != "target" {
+                copy_template(&source, &target, year, day)?;
+            }
+        } else {
+            write(&target, &render(&read(&source)?, year, day))?;
+        }
+    }
+    Ok(())
+}
+
+// Adds `"<year>/day_<day>"` to the workspace members.
+fn add_member(manifest: &str, year: u16, day: u8) -> Result<String, String> {
+    let entry = format!("    \"{}/day_{}\",", year, day);
+    insert_sorted(manifest, &entry, (year, day), "Cargo.toml", |line| {
+        let member = line.trim().trim_end_matches(',').trim_matches('"');
+        let (year, day) = member.split_once("/day_")?;
+        Some((year.parse().ok()?, day.parse().ok()?))
+    })
+}
+
+// Adds the day's crate to the runner's dependencies.
+fn add_dependency(manifest: &str, year: u16, day: u8) -> Result<String, String> {
+    let entry = format!("aoc_{0}_day_{1} = {{ path = \"../{0}/day_{1}\" }}", year, day);
+    insert_sorted(manifest, &entry, (year, day), "aoc/Cargo.toml", |line| {
+        let (name, _) = line.split_once(" = ")?;
+        let (year, day) = name.strip_prefix("aoc_")?.split_once("_day_")?;
+        Some((year.parse().ok()?, day.parse().ok()?))
+    })
+}
+
+// Adds the day to the runner's `DAYS`.
+fn add_to_registry(registry: &str, year: u16, day: u8) -> Result<String, String> {
+    let entry = format!("    day!({0}, {1}, aoc_{0}_day_{1}::Day{1}),", year, day);
+    insert_sorted(registry, &entry, (year, day), "aoc/src/registry.rs", |line| {
+        let mut fields = line.trim().strip_prefix("day!(")?.split(", ");
+        Some((fields.next()?.parse().ok()?, fields.next()?.parse().ok()?))
+    })
+}
+
+// Inserts the line `entry` among the lines of `text` that `key` recognises,
+// keeping those ordered by year and day.
+fn insert_sorted(
+    text: &str,
+    entry: &str,
+    new: (u16, u8),
+    file: &str,
+    key: impl Fn(&str) -> Option<(u16, u8)>,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let keyed: Vec<(usize, (u16, u8))> =
+        lines.iter().enumerate().filter_map(|(index, line)| Some((index, key(line)?))).collect();
+    if keyed.iter().any(|&(_, key)| key == new) {
+        return Err(format!("{} already lists {} day {}", file, new.0, new.1));
+    }
+    let index = match keyed.iter().find(|&&(_, key)| key > new) {
+        Some(&(index, _)) => index,
+        None => keyed.last().map(|&(index, _)| index + 1).ok_or(format!("found no days in {}", file))?,
+    };
+    lines.insert(index, entry);
+    let mut out = lines.join("\n");
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process::Command;
+
+    const MANIFEST: &str = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2015/day_1\",\n    \"2022/day_9\",\n]\n";
+    const RUNNER_MANIFEST: &str = "[dependencies]\naoc_common.workspace = true\naoc_2015_day_1 = { path = \"../2015/day_1\" }\n";
+    const REGISTRY: &str = "pub static DAYS: &[Day] = &[\n    day!(2015, 1, aoc_2015_day_1::Day1),\n    day!(2022, 9, aoc_2022_day_9::Day9),\n];\n";
+
+    #[test]
+    fn render_fills_in_placeholders() {
+        assert_eq!(render("aoc_{{year}}_day_{{day}}::Day{{day}}", 2022, 11), "aoc_2022_day_11::Day11");
+    }
+
+    #[test]
+    fn add_member_keeps_members_ordered() {
+        let manifest = add_member(MANIFEST, 2022, 1).unwrap();
+        assert!(manifest.contains("    \"2015/day_1\",\n    \"2022/day_1\",\n    \"2022/day_9\",\n"));
+        let manifest = add_member(MANIFEST, 2022, 10).unwrap();
+        assert!(manifest.contains("    \"2022/day_9\",\n    \"2022/day_10\",\n]\n"));
+    }
+
+    #[test]
+    fn add_dependency_appends_after_the_last_day() {
+        let manifest = add_dependency(RUNNER_MANIFEST, 2016, 3).unwrap();
+        assert!(manifest.ends_with(
+            "aoc_2015_day_1 = { path = \"../2015/day_1\" }\naoc_2016_day_3 = { path = \"../2016/day_3\" }\n"
+        ));
+    }
+
+    #[test]
+    fn add_to_registry_orders_by_year_then_day() {
+        let registry = add_to_registry(REGISTRY, 2015, 2).unwrap();
+        assert!(registry.contains("Day1),\n    day!(2015, 2, aoc_2015_day_2::Day2),\n    day!(2022, 9"));
+    }
+
+    #[test]
+    fn registering_a_day_twice_fails() {
+        assert!(add_member(MANIFEST, 2022, 9).is_err());
+        assert!(add_dependency(RUNNER_MANIFEST, 2015, 1).is_err());
+        assert!(add_to_registry(REGISTRY, 2015, 1).is_err());
+        assert!(add_to_registry("no days here\n", 2015, 1).is_err());
+    }
+
+    #[test]
+    fn new_day_creates_and_registers_the_crate() {
+        let root = env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER_MANIFEST).unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+        fs::create_dir_all(root.join(TEMPLATE).join("src")).unwrap();
+        for file in ["Cargo.toml", "README.md", "input", "src/lib.rs", "src/main.rs"] {
+            fs::copy(Path::new(ROOT).join(TEMPLATE).join(file), root.join(TEMPLATE).join(file)).unwrap();
+        }
+
+        let dir = root.join(new_day(&root, 2022, 11).unwrap());
+        let created = |path: &str| fs::read_to_string(dir.join(path)).unwrap();
+        assert!(created("Cargo.toml").contains("name = \"aoc_2022_day_11\""));
+        assert!(created("src/main.rs").contains("use aoc_2022_day_11::{Day11, INPUT};"));
+        assert!(created("src/lib.rs").contains("impl Solution for Day11 {"));
+        assert!(created("README.md").starts_with("# --- Day 11 ---"));
+        assert_eq!(created("input"), "");
+        assert!(fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap().contains("aoc_2022_day_11::Day11"));
+
+        let again = new_day(&root, 2022, 11);
+        fs::remove_dir_all(&root).unwrap();
+        assert!(again.unwrap_err().ends_with("already exists"));
+    }
+
+    #[test]
+    fn the_template_builds_and_passes_its_own_tests() {
+        let root = env::temp_dir().join(format!("aoc_template_{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let common = Path::new(ROOT).join("aoc_common").canonicalize().unwrap();
+        let manifest = format!(
+            "[workspace]\nresolver = \"2\"\nmembers = [\"2022/day_11\"]\n\n[workspace.package]\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace.dependencies]\naoc_common = {{ path = {:?} }}\n",
+            common
+        );
+        fs::write(root.join("Cargo.toml"), manifest).unwrap();
+        // pin the same dependency versions as the real workspace
+        fs::copy(Path::new(ROOT).join("Cargo.lock"), root.join("Cargo.lock")).unwrap();
+        copy_template(&Path::new(ROOT).join(TEMPLATE), &root.join(day_dir(2022, 11)), 2022, 11).unwrap();
+
+        let output = Command::new(env!("CARGO"))
+            .args(["test", "--offline", "--quiet"])
+            .current_dir(&root)
+            .env("CARGO_TARGET_DIR", Path::new(ROOT).join("target/template"))
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
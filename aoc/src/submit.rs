@@ -0,0 +1,264 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+// Where submissions are remembered, relative to this crate. The file is
+// ignored by git.
+pub const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../submissions.txt");
+
+// What a wrong answer's response said about it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+// How the server responded to an answer.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    // Wrong answers also lock out further answers for a while.
+    Wrong { hint: Hint, wait: Option<Duration> },
+    RateLimited { wait: Option<Duration> },
+    // The part was already solved, or part 1 isn't yet.
+    WrongLevel,
+    Unknown(String),
+}
+
+pub fn parse_response(html: &str) -> Verdict {
+    let text = text_of(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Hint::TooHigh
+        } else if text.contains("too low") {
+            Hint::TooLow
+        } else {
+            Hint::None
+        };
+        Verdict::Wrong { hint, wait: parse_wait(&text) }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited { wait: parse_wait(&text) }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+// The text of the page's <article>, which holds the verdict, without markup.
+fn text_of(html: &str) -> String {
+    let article = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Finds how long to wait in "You have 1m 30s left to wait" or "Please wait
+// one minute before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        let mut seconds = 0;
+        for part in left.split_whitespace() {
+            let unit = match part.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            seconds += part[..part.len() - 1].parse::<u64>().ok()? * unit;
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+    let (_, rest) = text.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(count * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(count)),
+        _ => None,
+    }
+}
+
+// Answers the server has rejected, and when it will take answers again.
+#[derive(Debug, Default, PartialEq)]
+pub struct Memory {
+    pub rejected: Vec<((u16, u8, u8), String, Hint)>,
+    // seconds since the Unix epoch
+    pub wait_until: u64,
+}
+
+impl Memory {
+    // Why `answer` should not be submitted, if it shouldn't be.
+    pub fn refuse(&self, key: (u16, u8, u8), answer: &str, now: u64) -> Option<String> {
+        if now < self.wait_until {
+            return Some(format!("the server asked to wait another {}s", self.wait_until - now));
+        }
+        let number = answer.parse::<i64>().ok();
+        for (_, rejected, hint) in self.rejected.iter().filter(|(k, _, _)| *k == key) {
+            if rejected == answer {
+                return Some(format!("{} was already rejected", answer));
+            }
+            match (number, rejected.parse::<i64>(), hint) {
+                (Some(number), Ok(high), Hint::TooHigh) if number >= high => {
+                    return Some(format!("{} was too high, so {} is too", high, number))
+                }
+                (Some(number), Ok(low), Hint::TooLow) if number <= low => {
+                    return Some(format!("{} was too low, so {} is too", low, number))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub fn reject(&mut self, key: (u16, u8, u8), answer: &str, hint: Hint) {
+        self.rejected.push((key, answer.into(), hint));
+    }
+
+    pub fn wait(&mut self, now: u64, wait: Option<Duration>) {
+        self.wait_until = now + wait.map_or(0, |wait| wait.as_secs());
+    }
+}
+
+// One rejected answer per line: `<year> <day> <part> <high|low|wrong> <answer>`,
+// plus the time the server takes answers again as `wait-until <seconds>`.
+pub fn parse(text: &str) -> Result<Memory, String> {
+    let mut memory = Memory::default();
+    for (index, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("line {}: expected <year> <day> <part> <high|low|wrong> <answer>", index + 1);
+        if let Some(until) = line.strip_prefix("wait-until ") {
+            memory.wait_until = until.parse().map_err(|_| invalid())?;
+            continue;
+        }
+        let fields: Vec<&str> = line.splitn(5, ' ').collect();
+        let [year, day, part, hint, answer] = fields[..] else { return Err(invalid()) };
+        let key = (year.parse().ok(), day.parse().ok(), part.parse().ok());
+        let (Some(year), Some(day), Some(part)) = key else { return Err(invalid()) };
+        let hint = match hint {
+            "high" => Hint::TooHigh,
+            "low" => Hint::TooLow,
+            "wrong" => Hint::None,
+            _ => return Err(invalid()),
+        };
+        memory.reject((year, day, part), answer, hint);
+    }
+    Ok(memory)
+}
+
+pub fn format(memory: &Memory) -> String {
+    let mut out = String::from("# year day part high|low|wrong answer\n");
+    for ((year, day, part), answer, hint) in memory.rejected.iter() {
+        let hint = match hint {
+            Hint::TooHigh => "high",
+            Hint::TooLow => "low",
+            Hint::None => "wrong",
+        };
+        writeln!(out, "{} {} {} {} {}", year, day, part, hint, answer).unwrap();
+    }
+    writeln!(out, "wait-until {}", memory.wait_until).unwrap();
+    out
+}
+
+pub fn load(path: &Path) -> io::Result<Memory> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Memory::default()),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn save(path: &Path, memory: &Memory) -> io::Result<()> {
+    fs::write(path, format(memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", article)
+    }
+
+    #[test]
+    fn parse_response_recognises_each_verdict() {
+        let correct = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.");
+        assert_eq!(parse_response(&correct), Verdict::Correct);
+        let high = page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/2022/day/5\">[Return to Day 5]</a>");
+        assert_eq!(
+            parse_response(&high),
+            Verdict::Wrong { hint: Hint::TooHigh, wait: Some(Duration::from_secs(60)) }
+        );
+        let low = page("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.");
+        assert_eq!(
+            parse_response(&low),
+            Verdict::Wrong { hint: Hint::TooLow, wait: Some(Duration::from_secs(300)) }
+        );
+        let recent = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.");
+        assert_eq!(parse_response(&recent), Verdict::RateLimited { wait: Some(Duration::from_secs(72)) });
+        let level = page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&level), Verdict::WrongLevel);
+        assert_eq!(parse_response("<p>Something <b>else</b></p>"), Verdict::Unknown("Something else".into()));
+    }
+
+    #[test]
+    fn refuse_remembers_rejected_answers() {
+        let mut memory = Memory::default();
+        memory.reject((2022, 5, 1), "ABC", Hint::None);
+        memory.reject((2022, 7, 1), "500", Hint::TooHigh);
+        memory.reject((2022, 7, 1), "100", Hint::TooLow);
+        assert!(memory.refuse((2022, 5, 1), "ABC", 0).is_some());
+        assert!(memory.refuse((2022, 5, 1), "ABD", 0).is_none());
+        assert!(memory.refuse((2022, 5, 2), "ABC", 0).is_none());
+        assert!(memory.refuse((2022, 7, 1), "600", 0).is_some());
+        assert!(memory.refuse((2022, 7, 1), "50", 0).is_some());
+        assert!(memory.refuse((2022, 7, 1), "250", 0).is_none());
+    }
+
+    #[test]
+    fn refuse_waits_for_the_server() {
+        let mut memory = Memory::default();
+        memory.wait(1000, Some(Duration::from_secs(60)));
+        assert_eq!(memory.refuse((2022, 5, 1), "ABC", 1030), Some("the server asked to wait another 30s".into()));
+        assert_eq!(memory.refuse((2022, 5, 1), "ABC", 1060), None);
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let mut memory = Memory::default();
+        memory.reject((2022, 7, 1), "500", Hint::TooHigh);
+        memory.reject((2022, 5, 2), "NOT IT", Hint::None);
+        memory.wait(1000, Some(Duration::from_secs(60)));
+        assert_eq!(parse(&format(&memory)), Ok(memory));
+        assert!(parse("2022 7 1 higher 500").is_err());
+        assert!(parse("wait-until soon").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+// `--trace`: shows the days' `tracing` events on stderr, e.g. directories
+// entered and left on 2022 day 7 with `--trace day7=debug`.
+use std::collections::HashMap;
+use std::io;
+use std::str::FromStr;
+
+use aoc_common::tracing::Level;
+use tracing_subscriber::filter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::registry::Day;
+
+// One comma-separated part of `--trace`: a day, or every day, and the most
+// detailed level to show for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Directive {
+    pub day: Option<u8>,
+    pub level: Level,
+}
+
+// Parses directives like "day7=debug", "day5" (which means debug) and
+// "trace" (every day at that level), separated by commas.
+pub fn parse(spec: &str) -> Result<Vec<Directive>, String> {
+    spec.split(',').map(str::trim).map(|directive| {
+        let (target, level) = match directive.split_once('=') {
+            Some((target, level)) => (target, Some(level)),
+            None if directive.starts_with("day") => (directive, None),
+            None => ("", Some(directive)),
+        };
+        let day = match target {
+            "" => None,
+            _ => Some(target.strip_prefix("day").and_then(|day| day.parse().ok()).ok_or(format!(
+                "invalid trace target {:?}: expected a day like day7",
+                target
+            ))?),
+        };
+        let level = match level {
+            Some(level) => Level::from_str(level).map_err(|_| {
+                format!("invalid trace level {:?}: expected error, warn, info, debug or trace", level)
+            })?,
+            None => Level::DEBUG,
+        };
+        Ok(Directive { day, level })
+    }).collect()
+}
+
+// The most detailed level to show for each of the crates of `days`, whose
+// events are named after them, e.g. aoc_2022_day_7.
+fn levels(directives: &[Directive], days: &[Day]) -> HashMap<String, Level> {
+    let mut levels = HashMap::new();
+    for directive in directives {
+        for day in days.iter().filter(|day| directive.day.is_none_or(|wanted| day.day == wanted)) {
+            levels.insert(format!("aoc_{}_day_{}", day.year, day.day), directive.level);
+        }
+    }
+    levels
+}
+
+// Whether `levels` shows an event at `level` from `target`. This matches the
+// crate exactly, where tracing's own filters match prefixes and would let day
+// 1's events bring day 10's along.
+fn enabled(levels: &HashMap<String, Level>, target: &str, level: &Level) -> bool {
+    let krate = target.split("::").next().unwrap_or(target);
+    levels.get(krate).is_some_and(|most| level <= most)
+}
+
+// Starts printing the events `directives` ask for to stderr.
+pub fn init(directives: &[Directive], days: &[Day]) {
+    let levels = levels(directives, days);
+    let layer = tracing_subscriber::fmt::layer().with_writer(io::stderr).without_time();
+    let filter = filter::filter_fn(move |metadata| enabled(&levels, metadata.target(), metadata.level()));
+    tracing_subscriber::registry().with(layer).with(filter).init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn parse_reads_days_and_levels() {
+        assert_eq!(
+            parse("day7=debug, day5,trace"),
+            Ok(vec![
+                Directive { day: Some(7), level: Level::DEBUG },
+                Directive { day: Some(5), level: Level::DEBUG },
+                Directive { day: None, level: Level::TRACE },
+            ])
+        );
+        assert_eq!(parse("day10=TRACE"), Ok(vec![Directive { day: Some(10), level: Level::TRACE }]));
+        assert!(parse("day7=loud").is_err());
+        assert!(parse("daily=debug").is_err());
+        assert!(parse("loud").is_err());
+    }
+
+    #[test]
+    fn levels_match_each_days_crate() {
+        let levels = levels(&parse("day1=info,day7=trace").unwrap(), DAYS);
+        assert!(enabled(&levels, "aoc_2015_day_1", &Level::INFO));
+        assert!(enabled(&levels, "aoc_2022_day_1::parse", &Level::WARN));
+        assert!(!enabled(&levels, "aoc_2022_day_1", &Level::DEBUG));
+        assert!(!enabled(&levels, "aoc_2022_day_10", &Level::ERROR));
+        assert!(enabled(&levels, "aoc_2022_day_7", &Level::TRACE));
+        assert!(!enabled(&levels, "ureq", &Level::ERROR));
+    }
+}
@@ -0,0 +1,82 @@
+// Simulations that can be watched step by step: each step is drawn as a
+// `Frame`, and the runner plays the frames back (see `aoc watch`).
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+// One step of a simulation, drawn as characters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub grid: Grid<char>,
+    // what is happening, shown above the grid
+    pub caption: String,
+    // a cell to keep in view when the grid is too big to show whole
+    pub focus: Option<(usize, usize)>,
+}
+
+impl Frame {
+    pub fn new(grid: Grid<char>, caption: impl Into<String>) -> Self {
+        Self { grid, caption: caption.into(), focus: None }
+    }
+
+    pub fn focus(self, x: usize, y: usize) -> Self {
+        Self { focus: Some((x, y)), ..self }
+    }
+}
+
+// The frames of a simulation, produced as they are watched.
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+// A solution whose parts can be watched as they are worked out.
+pub trait Simulation: Solution {
+    // The frames of `part`'s simulation, first to last.
+    fn frames(self, part: u8) -> Frames;
+}
+
+// Parses `input` and starts simulating `part`.
+pub fn frames<S: Simulation>(input: &str, part: u8) -> Result<Frames, ParseError> {
+    Ok(S::parse(input)?.frames(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts up to each number in the input, a frame per count
+    struct Count(Vec<usize>);
+
+    impl Solution for Count {
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            crate::error::parse_lines(input, crate::error::number).map(Count)
+        }
+
+        fn part1(&self) -> usize {
+            self.0.iter().sum()
+        }
+
+        fn part2(&self) -> usize {
+            0
+        }
+    }
+
+    impl Simulation for Count {
+        fn frames(self, _part: u8) -> Frames {
+            Box::new(self.0.into_iter().flat_map(|n| {
+                (1..=n).map(move |i| Frame::new(Grid::new(i, 1, '#'), format!("{}/{}", i, n)).focus(i - 1, 0))
+            }))
+        }
+    }
+
+    #[test]
+    fn frames_parses_and_simulates() {
+        let frames: Vec<Frame> = frames::<Count>("2\n1\n", 1).unwrap().collect();
+        let captions: Vec<&str> = frames.iter().map(|frame| frame.caption.as_str()).collect();
+        assert_eq!(captions, ["1/2", "2/2", "1/1"]);
+        assert_eq!(frames[1].grid.to_string(), "##");
+        assert_eq!(frames[1].focus, Some((1, 0)));
+        assert!(super::frames::<Count>("x\n", 1).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    // untimed runs before sampling, to warm caches and the allocator
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { warmup: 3, samples: 20 }
+    }
+}
+
+// Summary of repeated timings of one step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let median = if n % 2 == 1 {
+            nanos[n / 2]
+        } else {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        };
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            samples: n,
+            mean: from_nanos(mean),
+            median: from_nanos(median),
+            stddev: from_nanos(variance.sqrt()),
+            min: sorted[0],
+        }
+    }
+}
+
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+// Timings of each step of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+// Times `f` `options.samples` times, after `options.warmup` untimed runs.
+// There has to be at least one sample to summarise.
+pub fn measure<T>(options: &Options, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..options.warmup {
+        black_box(f());
+    }
+    let samples: Vec<Duration> = (0..options.samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+// Benchmarks parsing and each part of `S` separately. The parts are timed
+// against a single parsed input.
+pub fn bench<S: Solution>(input: &str, options: &Options) -> Result<Bench, ParseError> {
+    let solution = S::parse(input)?;
+    Ok(Bench {
+        parse: measure(options, || S::parse(black_box(input))),
+        part1: measure(options, || solution.part1()),
+        part2: measure(options, || solution.part2()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn stats_summarise_odd_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 9, 4, 6]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        // population stddev of [2, 4, 4, 6, 9] is sqrt(5.6)
+        assert_eq!(stats.stddev, Duration::from_nanos(2_366_432));
+    }
+
+    #[test]
+    fn stats_median_of_even_samples_is_the_midpoint() {
+        let stats = Stats::from_samples(&millis(&[1, 3, 2, 4]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_samples() {
+        let mut calls = 0;
+        let stats = measure(&Options { warmup: 2, samples: 5 }, || calls += 1);
+        assert_eq!(calls, 7);
+        assert_eq!(stats.samples, 5);
+    }
+}
@@ -0,0 +1,206 @@
+// Differential testing: a day's production code against a naive reference
+// that is easier to trust, on inputs from its `Generator`. When they disagree
+// the input is shrunk, by dropping lines and columns and simplifying
+// characters, to the smallest one on which they still do, so the report shows
+// a case small enough to work through by hand.
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::random::{self, Generator};
+
+// how many generated inputs each property is checked on
+pub const CASES: u64 = 100;
+
+// An input on which the reference and the solver disagree, already shrunk.
+#[derive(Debug, PartialEq)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub size: usize,
+    // the generated input and the shrunk one
+    pub generated: String,
+    pub input: String,
+    // what each gave on the shrunk input, or the message it panicked with
+    pub expected: Result<String, String>,
+    pub found: Result<String, String>,
+}
+
+// Checks `solver` against `reference` on `CASES` generated inputs, growing
+// from size 1 to nearly `G::SIZE`, and returns the first disagreement, shrunk.
+pub fn check<G, T>(reference: impl Fn(&G) -> T, solver: impl Fn(&G) -> T) -> Option<Disagreement>
+where
+    G: Generator,
+    T: PartialEq + Debug,
+{
+    let outcomes = |input: &str| {
+        let solution = catching(|| G::parse(input)).ok()?.ok()?;
+        let expected = catching(|| reference(&solution));
+        let found = catching(|| solver(&solution));
+        (expected != found).then(|| (describe(expected), describe(found)))
+    };
+    let (seed, size, generated) = (0..CASES)
+        .map(|seed| {
+            // mostly small inputs, which are quick and more likely to hit edges
+            let size = 1 + (seed * seed) as usize * G::SIZE / (CASES * CASES) as usize;
+            (seed, size, random::generate::<G>(seed, Some(size)))
+        })
+        .find(|(_, _, generated)| outcomes(generated).is_some())?;
+    let input = shrink(&generated, |input| outcomes(input).is_some());
+    let (expected, found) = outcomes(&input).unwrap();
+    Some(Disagreement { seed, size, generated, input, expected, found })
+}
+
+// Fails, showing the shrunk input, if `solver` and `reference` disagree on
+// any of the generated inputs.
+pub fn assert_agree<G, T>(reference: impl Fn(&G) -> T, solver: impl Fn(&G) -> T)
+where
+    G: Generator,
+    T: PartialEq + Debug,
+{
+    if let Some(failure) = check(reference, solver) {
+        panic!(
+            "the solver disagrees with the reference on seed {} size {}, shrunk from {} bytes to:\n{}\nexpected {:?}, found {:?}",
+            failure.seed,
+            failure.size,
+            failure.generated.len(),
+            failure.input,
+            failure.expected,
+            failure.found
+        );
+    }
+}
+
+// Shrinks `input`, which `fails`, one step at a time: each step takes the
+// first smaller or simpler input that still fails, until none does.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut input = input.to_string();
+    while let Some(smaller) = candidates(&input).into_iter().find(|candidate| fails(candidate)) {
+        input = smaller;
+    }
+    input
+}
+
+// Inputs one step smaller or simpler than `input`: without one of its lines,
+// without one of its columns (a character from every line long enough to
+// have it), or with one character swapped for the simplest of its kind.
+fn candidates(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let join = |lines: Vec<String>| lines.iter().map(|line| format!("{}\n", line)).collect::<String>();
+    let mut candidates = Vec::new();
+    if lines.len() > 1 {
+        for skip in 0..lines.len() {
+            let kept = lines.iter().enumerate().filter(|&(index, _)| index != skip);
+            candidates.push(join(kept.map(|(_, line)| line.to_string()).collect()));
+        }
+    }
+    let widest = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    for column in 0..widest {
+        let without = |line: &&str| {
+            line.chars().enumerate().filter(|&(index, _)| index != column).map(|(_, c)| c).collect::<String>()
+        };
+        candidates.push(join(lines.iter().map(without).collect()));
+    }
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let simplest = match c {
+                '0'..='9' => '0',
+                'a'..='z' => 'a',
+                'A'..='Z' => 'A',
+                _ => continue,
+            };
+            if c != simplest {
+                let mut simpler: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+                simpler[y] = line.chars().enumerate().map(|(i, c)| if i == x { simplest } else { c }).collect();
+                candidates.push(join(simpler));
+            }
+        }
+    }
+    candidates.retain(|candidate| candidate != input);
+    candidates
+}
+
+// Runs `f`, turning a panic into an error with its message.
+fn catching<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".into())
+    })
+}
+
+fn describe<T: Debug>(outcome: Result<T, String>) -> Result<String, String> {
+    outcome.map(|value| format!("{:?}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+    use crate::{ParseError, Solution};
+
+    // a number per line
+    struct Numbers(Vec<u32>);
+
+    impl Solution for Numbers {
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            crate::parse_lines(input, crate::number).map(Numbers)
+        }
+
+        fn part1(&self) -> u32 {
+            self.0.iter().copied().max().unwrap_or(0)
+        }
+
+        fn part2(&self) -> u32 {
+            self.0.iter().sum()
+        }
+    }
+
+    impl Generator for Numbers {
+        const SIZE: usize = 50;
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size).map(|_| format!("{}\n", rng.below(1000))).collect()
+        }
+    }
+
+    #[test]
+    fn agreeing_solvers_pass() {
+        assert_eq!(check(Numbers::part1, |numbers: &Numbers| numbers.0.iter().fold(0, |max, &n| max.max(n))), None);
+    }
+
+    #[test]
+    fn disagreements_are_shrunk() {
+        // misses the last number
+        let failure = check(Numbers::part1, |numbers: &Numbers| {
+            numbers.0[..numbers.0.len() - 1].iter().copied().max().unwrap_or(0)
+        })
+        .unwrap();
+        assert!(failure.generated.len() > failure.input.len());
+        // a single number, no more than one digit long, that isn't 0
+        assert_eq!(failure.input.len(), 2, "{:?}", failure.input);
+        assert_ne!(failure.input, "0\n");
+        assert_eq!(failure.found, Ok("0".into()));
+    }
+
+    #[test]
+    fn panics_are_disagreements() {
+        let failure = check(Numbers::part2, |numbers: &Numbers| {
+            assert!(numbers.0.iter().all(|&n| n < 900), "too big");
+            numbers.part2()
+        });
+        assert!(matches!(failure, Some(Disagreement { found: Err(ref message), .. }) if message == "too big"), "{:?}", failure);
+    }
+
+    #[test]
+    fn candidates_drop_lines_and_columns() {
+        let candidates = candidates("12\n34\n");
+        assert!(candidates.contains(&"34\n".to_string()));
+        assert!(candidates.contains(&"2\n4\n".to_string()));
+        assert!(candidates.contains(&"10\n34\n".to_string()));
+        assert!(!candidates.contains(&"12\n34\n".to_string()));
+    }
+}
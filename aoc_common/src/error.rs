@@ -0,0 +1,182 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// A problem with the puzzle input, and where in the input it was found.
+// Lines and columns count from 1; 0 means "not located yet".
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // An error about `token`, to be placed in the input later with `at`.
+    pub fn new(message: impl Into<String>, token: &str) -> Self {
+        Self { line: 0, column: 0, token: token.into(), message: message.into() }
+    }
+
+    // An error about `token`, a slice of the line `text`, so the column is
+    // exact even if the same token appears earlier in the line.
+    pub fn in_line(message: impl Into<String>, text: &str, token: &str) -> Self {
+        Self::new(message, token).within(text, token)
+    }
+
+    // Sets the column to where `token`, a slice of the line `text`, starts.
+    pub fn within(mut self, text: &str, token: &str) -> Self {
+        self.column = column_of(text, offset_of(token, text).or_else(|| text.find(token)));
+        self
+    }
+
+    // Moves the column of an error found in `part`, a slice of the line
+    // `text`, so it counts from the start of `text` instead. Without a known
+    // column, the column is where the token first appears in `part`.
+    pub fn inside(mut self, text: &str, part: &str) -> Self {
+        if self.column == 0 {
+            self.column = column_of(part, part.find(&self.token));
+        }
+        self.column += column_of(text, offset_of(part, text)) - 1;
+        self
+    }
+
+    // Places the error on line number `line`, whose contents are `text`,
+    // unless it has been placed already. Without a known column, the column
+    // is where the token first appears in `text`.
+    pub fn at(mut self, line: usize, text: &str) -> Self {
+        if self.line == 0 {
+            self.line = line;
+        }
+        if self.column == 0 {
+            self.column = column_of(text, text.find(&self.token));
+        }
+        self
+    }
+
+    // Renders the error with the offending line of `input` and a caret
+    // under the offending token, e.g.
+    //
+    //     error: unknown hand "Q"
+    //      --> input:3:1
+    //       |
+    //     3 | Q X
+    //       | ^
+    pub fn diagnostic(&self, input: &str, source: &str) -> String {
+        let mut out = format!("error: {}\n", self);
+        let text = match input.lines().nth(self.line.saturating_sub(1)) {
+            Some(text) if self.line > 0 => text,
+            _ => return out,
+        };
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = " ".repeat(self.column.saturating_sub(1));
+        let carets = "^".repeat(self.token.chars().count().max(1));
+        out += &format!("{}--> {}:{}:{}\n", gutter, source, self.line, self.column);
+        out += &format!("{} |\n", gutter);
+        out += &format!("{} | {}\n", number, text);
+        out += &format!("{} | {}{}\n", gutter, indent, carets);
+        out
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:?}", self.message, self.token)?;
+        if self.line > 0 {
+            write!(f, " at line {}, column {}", self.line, self.column)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+fn column_of(text: &str, offset: Option<usize>) -> usize {
+    1 + offset.map(|offset| text[..offset].chars().count()).unwrap_or(0)
+}
+
+// Where `token` starts within `text`, if it is a slice of it.
+fn offset_of(token: &str, text: &str) -> Option<usize> {
+    let start = text.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    (token_start >= start && token_start + token.len() <= start + text.len())
+        .then(|| token_start - start)
+}
+
+// Parses `token` as a number, e.g. a distance or an amount.
+pub fn number<T: FromStr>(token: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::new("expected a number, found", token))
+}
+
+// Parses every line of `input` with `parse`, locating any error on its line.
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|err| err.at(index + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_line_finds_the_column_of_a_sliced_token() {
+        let line = "addx addx";
+        let err = ParseError::in_line("bad", line, &line[5..]).at(4, line);
+        assert_eq!((err.line, err.column), (4, 6));
+    }
+
+    #[test]
+    fn at_searches_for_a_copied_token() {
+        let err = ParseError::new("bad", "x").at(1, "move x from 1");
+        assert_eq!(err.column, 6);
+    }
+
+    #[test]
+    fn parse_lines_reports_the_failing_line() {
+        let err = parse_lines("1\n2\nthree\n", number::<u32>).unwrap_err();
+        assert_eq!(err, ParseError {
+            line: 3,
+            column: 1,
+            token: "three".into(),
+            message: "expected a number, found".into(),
+        });
+        assert_eq!(parse_lines("1\n2\n", number::<u32>), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn inside_counts_columns_from_the_whole_line() {
+        let line = "2-4,6-8x";
+        let part = &line[4..];
+        let err = ParseError::in_line("bad", part, &part[2..]).inside(line, part);
+        assert_eq!(err.column, 7);
+        let err = ParseError::new("bad", "8x").inside(line, part);
+        assert_eq!(err.column, 7);
+    }
+
+    #[test]
+    fn at_keeps_an_existing_location() {
+        let err = ParseError::new("bad", "x").at(2, "x x").at(7, "y x");
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn diagnostic_points_at_the_token() {
+        let input = "noop\naddx 1x\n";
+        let err = ParseError::new("expected a number, found", "1x").at(2, "addx 1x");
+        let expected = "\
+error: expected a number, found \"1x\" at line 2, column 6
+ --> input:2:6
+  |
+2 | addx 1x
+  |      ^^
+";
+        assert_eq!(err.diagnostic(input, "input"), expected);
+    }
+}
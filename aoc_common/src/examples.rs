@@ -0,0 +1,189 @@
+use crate::solution::Solution;
+
+// A worked example from a day's README: an input, and the answer the puzzle
+// text gives for it in one part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+    // README line the answer was found on, for reporting failures.
+    pub line: usize,
+}
+
+// Extracts the worked examples from a puzzle description. Example inputs are
+// fenced with an `example` info string and answers are in bold; an answer
+// refers to the most recent example input, so part two reuses part one's
+// input unless it fences one of its own. Short examples can instead be
+// written inline, as code spans on the same line as their answer:
+//
+//     - `(((` and `(()(()(` both result in floor **3**.
+//
+// Answers spanning several lines are fenced with an `answer` info string.
+// Other fenced blocks are illustrations and are ignored.
+pub fn examples(readme: &str) -> Vec<Example> {
+    let mut examples = Vec::new();
+    let mut part = 1;
+    let mut input: Option<String> = None;
+    let mut fence: Option<(&str, usize, Vec<&str>)> = None;
+    for (index, line) in readme.lines().enumerate() {
+        if let Some(info) = line.strip_prefix("```") {
+            match fence.take() {
+                None => fence = Some((info.trim(), index + 1, Vec::new())),
+                Some(("example", _, body)) => input = Some(body.join("\n") + "\n"),
+                Some(("answer", line, body)) => {
+                    if let Some(input) = &input {
+                        examples.push(Example { part, input: input.clone(), answer: body.join("\n"), line });
+                    }
+                }
+                Some(_) => {}
+            }
+            continue;
+        }
+        if let Some((_, _, body)) = &mut fence {
+            body.push(line);
+            continue;
+        }
+        if line.contains("--- Part Two ---") {
+            part = 2;
+        }
+        let inline = spans(line, "`");
+        for answer in spans(line, "**") {
+            let inputs = match (&inline[..], &input) {
+                ([], Some(input)) => vec![input.as_str()],
+                _ => inline.clone(),
+            };
+            for input in inputs {
+                examples.push(Example { part, input: input.into(), answer: answer.into(), line: index + 1 });
+            }
+        }
+    }
+    examples
+}
+
+// The text between each pair of `delimiter`s in `line`.
+fn spans<'a>(line: &'a str, delimiter: &str) -> Vec<&'a str> {
+    let pieces: Vec<&str> = line.split(delimiter).collect();
+    if pieces.len().is_multiple_of(2) {
+        return Vec::new();
+    }
+    pieces.into_iter().skip(1).step_by(2).collect()
+}
+
+// Runs `S` on every example in `readme`, describing each one it gets wrong.
+pub fn check<S: Solution>(readme: &str) -> Vec<String> {
+    let mut failures = Vec::new();
+    for example in examples(readme) {
+        let solution = match S::parse(&example.input) {
+            Ok(solution) => solution,
+            Err(err) => {
+                failures.push(format!("line {}: could not parse the example: {}", example.line, err));
+                continue;
+            }
+        };
+        let answer = match example.part {
+            1 => solution.part1().to_string(),
+            _ => solution.part2().to_string(),
+        };
+        if answer != example.answer {
+            failures.push(format!(
+                "line {}: part {} expected {:?}, got {:?}",
+                example.line, example.part, example.answer, answer
+            ));
+        }
+    }
+    failures
+}
+
+// Panics unless `readme` has worked examples for both parts and `S` gets all
+// of them right.
+pub fn assert_examples<S: Solution>(readme: &str) {
+    let examples = examples(readme);
+    for part in 1..=2 {
+        assert!(examples.iter().any(|e| e.part == part), "the README has no worked examples for part {}", part);
+    }
+    let failures = check::<S>(readme);
+    assert!(failures.is_empty(), "README examples failed:\n{}", failures.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{number, parse_lines, ParseError};
+
+    const README: &str = "\
+--- Day 0: Sums ---
+
+For example:
+
+```example
+1
+2
+```
+
+```
+an illustration, not an input
+```
+
+The sum is **3**.
+
+--- Part Two ---
+
+  - `4` and `5` both give **9**.
+
+```answer
+[1, 2]
+```
+";
+
+    struct Sum(Vec<u32>);
+
+    impl Solution for Sum {
+        type Part1 = u32;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            Ok(Sum(parse_lines(input, number)?))
+        }
+
+        fn part1(&self) -> u32 {
+            self.0.iter().sum()
+        }
+
+        fn part2(&self) -> String {
+            format!("{:?}", self.0)
+        }
+    }
+
+    #[test]
+    fn examples_pair_answers_with_inputs() {
+        let example = |part, input: &str, answer: &str, line| Example { part, input: input.into(), answer: answer.into(), line };
+        assert_eq!(
+            examples(README),
+            vec![
+                example(1, "1\n2\n", "3", 14),
+                example(2, "4", "9", 18),
+                example(2, "5", "9", 18),
+                example(2, "1\n2\n", "[1, 2]", 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_wrong_answers() {
+        let failures = check::<Sum>(README);
+        assert_eq!(
+            failures,
+            vec![
+                "line 18: part 2 expected \"9\", got \"[4]\"",
+                "line 18: part 2 expected \"9\", got \"[5]\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_unparsable_examples() {
+        let failures = check::<Sum>("```example\nx\n```\n**1**\n");
+        assert_eq!(failures, vec!["line 4: could not parse the example: expected a number, found \"x\" at line 1, column 1"]);
+    }
+}
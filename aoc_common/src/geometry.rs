@@ -0,0 +1,384 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+// Positions and steps on an unbounded plane. As in `Grid`, `x` grows to the
+// right and `y` grows downwards, so "up" is negative `y`.
+
+// A position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+// A displacement between two positions, e.g. a step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point) -> usize {
+        (other - self).chebyshev()
+    }
+
+    // The positions sharing an edge with this one, clockwise from up.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |direction| self + direction.step())
+    }
+
+    // The positions touching this one, corners included, clockwise from up.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().flat_map(move |direction| {
+            let step = direction.step();
+            [self + step, self + step + step.rotate_right()]
+        })
+    }
+}
+
+// A `Grid` position.
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    // The number of orthogonal steps it takes.
+    pub fn manhattan(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    // The number of steps it takes when diagonal steps are allowed.
+    pub fn chebyshev(self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    // Each component clamped to -1, 0 or 1: the single step, diagonals
+    // included, that heads the same way.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    // A quarter turn clockwise, as seen on screen.
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    // A quarter turn counterclockwise, as seen on screen.
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, step: Vec2) -> Point {
+        Point::new(self.x + step.x, self.y + step.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, step: Vec2) {
+        *self = *self + step;
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, step: Vec2) -> Point {
+        self + -step
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, step: Vec2) {
+        *self = *self - step;
+    }
+}
+
+// The displacement that takes `other` to `self`.
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Point) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        self + -other
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, times: isize) -> Vec2 {
+        Vec2::new(self.x * times, self.y * times)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from up.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    // A step of one in this direction.
+    pub fn step(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    // Reads a direction written as a letter (U/R/D/L or N/E/S/W, either case)
+    // or an arrow (^ > v < or ↑ → ↓ ←).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'u' | 'N' | 'n' | '^' | '↑' => Some(Direction::Up),
+            'R' | 'r' | 'E' | 'e' | '>' | '→' => Some(Direction::Right),
+            'D' | 'd' | 'S' | 's' | 'v' | '↓' => Some(Direction::Down),
+            'L' | 'l' | 'W' | 'w' | '<' | '←' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(token: &str) -> Result<Self, ParseError> {
+        let mut chars = token.chars();
+        match (chars.next().and_then(Direction::from_char), chars.next()) {
+            (Some(direction), None) => Ok(direction),
+            _ => Err(ParseError::new("unknown direction", token)),
+        }
+    }
+}
+
+// The smallest rectangle holding a set of points, edges included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    // The bounds of a single point.
+    pub fn at(point: Point) -> Self {
+        Self { min: point, max: point }
+    }
+
+    // The bounds of `points`, or `None` if there are none.
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::at(first), Self::including))
+    }
+
+    // Grown, if need be, to hold `point` too.
+    pub fn including(self, point: Point) -> Self {
+        Self {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    // Every point inside, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_and_vectors_combine() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(b - Vec2::new(3, -4), a);
+        assert_eq!(Vec2::new(1, 2) * 3 - Vec2::new(1, 1), Vec2::new(2, 5));
+        assert_eq!(-Vec2::new(1, -2), Vec2::new(-1, 2));
+        let mut c = a;
+        c += Vec2::new(1, 1);
+        c -= Vec2::new(0, 2);
+        assert_eq!(c, Point::new(2, 1));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(Vec2::ZERO.chebyshev(), 0);
+        assert_eq!(Point::from((3, 0)).manhattan(Point::from((1, 2))), 4);
+    }
+
+    #[test]
+    fn signum_clamps_each_component() {
+        assert_eq!(Vec2::new(5, -3).signum(), Vec2::new(1, -1));
+        assert_eq!(Vec2::new(0, 2).signum(), Vec2::new(0, 1));
+        assert_eq!(Vec2::ZERO.signum(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn rotations_turn_a_quarter() {
+        let v = Vec2::new(2, -1);
+        assert_eq!(v.rotate_right(), Vec2::new(1, 2));
+        assert_eq!(v.rotate_left(), Vec2::new(-1, -2));
+        assert_eq!(v.rotate_right().rotate_right(), -v);
+        assert_eq!(v.rotate_left().rotate_right(), v);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().step(), direction.step().rotate_right());
+            assert_eq!(direction.turn_left().step(), direction.step().rotate_left());
+            assert_eq!(direction.reverse().step(), -direction.step());
+        }
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+    }
+
+    #[test]
+    fn directions_parse_from_letters_and_arrows() {
+        for (tokens, direction) in [
+            (["U", "N", "^", "↑"], Direction::Up),
+            (["R", "E", ">", "→"], Direction::Right),
+            (["D", "S", "v", "↓"], Direction::Down),
+            (["L", "W", "<", "←"], Direction::Left),
+        ] {
+            for token in tokens {
+                assert_eq!(token.parse(), Ok(direction), "{}", token);
+            }
+        }
+        assert_eq!("x".parse::<Direction>(), Err(ParseError::new("unknown direction", "x")));
+        assert!("UR".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let neighbours: Vec<Point> = Point::ORIGIN.neighbours4().collect();
+        assert_eq!(neighbours, [Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)]);
+        let neighbours: Vec<Point> = Point::ORIGIN.neighbours8().collect();
+        assert_eq!(neighbours.len(), 8);
+        assert!(neighbours.iter().all(|&point| Point::ORIGIN.chebyshev(point) == 1));
+        assert_eq!(neighbours[1], Point::new(1, -1));
+    }
+
+    #[test]
+    fn bounds_hold_every_point() {
+        let points = [Point::new(2, 3), Point::new(-1, 5), Point::new(0, 4)];
+        let bounds = Bounds::of(points).unwrap();
+        assert_eq!(bounds, Bounds { min: Point::new(-1, 3), max: Point::new(2, 5) });
+        assert_eq!((bounds.width(), bounds.height()), (4, 3));
+        assert!(points.iter().all(|&point| bounds.contains(point)));
+        assert!(!bounds.contains(Point::new(3, 4)));
+        assert_eq!(bounds.points().count(), 12);
+        assert_eq!(bounds.points().next(), Some(bounds.min));
+        assert_eq!(Bounds::of([]), None);
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+// A rectangular grid of cells stored row by row in a single `Vec`. Positions
+// are `(x, y)`: `x` counts columns from the left, `y` rows from the top.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// The four orthogonal steps, clockwise from up.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// All eight steps to a touching cell, clockwise from up.
+pub const ADJACENT: [(isize, isize); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect();
+        Self { width, height, cells }
+    }
+
+    // Lays `cells` out in rows of `width`. Panics unless they fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {}",
+            cells.len(),
+            width
+        );
+        Self { width, height: cells.len() / width, cells }
+    }
+
+    // Parses a map with one character per cell, e.g. "#.#\n..#\n", using `f`
+    // to read each character. Characters `f` rejects are reported as not
+    // being `expected`.
+    pub fn parse(input: &str, expected: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        for (index, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    let token = &line[i..i + c.len_utf8()];
+                    ParseError::in_line(format!("expected {}, found", expected), line, token).at(index + 1, line)
+                })?;
+                cells.push(cell);
+            }
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(width) if width != found => {
+                    let message = format!("expected a row of {} cells, found", width);
+                    return Err(ParseError::in_line(message, line, line).at(index + 1, line));
+                }
+                Some(_) => {}
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self::from_vec(width, cells)),
+            _ => Err(ParseError::new("expected a grid, found", "").at(1, "")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, x: isize, y: isize) -> Option<usize> {
+        let inside = (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y);
+        inside.then(|| y as usize * self.width + x as usize)
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.offset(x, y).is_some()
+    }
+
+    // The cell at `(x, y)`, or `None` off the edge of the grid. Signed so that
+    // stepping off the top or left edge needs no special case.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.offset(x, y).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.offset(x, y).map(|offset| &mut self.cells[offset])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // The cells of column `x`, from the top.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside a grid {} wide", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    // Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // The cells met walking from `(x, y)` in steps of `(dx, dy)` until the
+    // edge, not counting the cell walked from.
+    pub fn ray(&self, x: usize, y: usize, (dx, dy): (isize, isize)) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let start = (x as isize, y as isize);
+        iter::successors(Some(start), move |&(x, y)| Some((x + dx, y + dy)))
+            .skip(1)
+            .map_while(|(x, y)| Some(((x as usize, y as usize), self.get(x, y)?)))
+    }
+
+    // The positions of the cells a step of `steps` away from `(x, y)`.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        steps: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        steps
+            .iter()
+            .map(move |&(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(|&(x, y)| self.contains(x, y))
+            .map(|(x, y)| (x as usize, y as usize))
+    }
+
+    // The up to 4 cells sharing an edge with `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ORTHOGONAL)
+    }
+
+    // The up to 8 cells touching `(x, y)`, corners included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ADJACENT)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    // Mirrors the grid in its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(self.width - 1 - y, x)].clone())
+    }
+
+    // Draws the grid with one character per cell, a row per line.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        self.rows().map(|row| row.iter().map(&f).collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// A row per line. Cells that display as a single character are run together,
+// like the map they were parsed from; wider ones are lined up in columns.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<String> = self.cells.iter().map(|cell| cell.to_string()).collect();
+        let width = cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0);
+        for (y, row) in cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            if width == 1 {
+                write!(f, "{}", row.concat())?;
+            } else {
+                let padded: Vec<String> = row.iter().map(|cell| format!("{:>width$}", cell)).collect();
+                write!(f, "{}", padded.join(" "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("ab\ncd\nef\n", "a letter", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_a_character_map() {
+        let grid = Grid::parse("#.#\n..#\n", "# or .", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), [false, false, true]);
+    }
+
+    #[test]
+    fn parse_locates_bad_cells_and_ragged_rows() {
+        let digit = |c: char| c.to_digit(10);
+        let err = Grid::parse("123\n4x6\n", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+        assert_eq!(err.message, "expected a digit, found");
+        let err = Grid::parse("123\n45\n", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "45"));
+        assert!(Grid::parse("", "a digit", digit).is_err());
+        assert!(Grid::parse("\n\n", "a digit", digit).is_err());
+    }
+
+    #[test]
+    fn get_is_bounds_checked() {
+        let grid = letters();
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, -1), None);
+        assert_eq!(grid[(0, 1)], 'c');
+        let mut grid = grid;
+        *grid.get_mut(1, 1).unwrap() = 'D';
+        grid[(0, 0)] = 'A';
+        assert_eq!(grid.to_string(), "Ab\ncD\nef");
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0) is outside a 2x3 grid")]
+    fn index_panics_off_the_grid() {
+        let _ = letters()[(2, 0)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = letters();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [['a', 'b'], ['c', 'd'], ['e', 'f']]);
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+        assert_eq!(grid.column(0).rev().collect::<String>(), "eca");
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ace", "bdf"]);
+    }
+
+    #[test]
+    fn cells_come_row_by_row() {
+        let grid = letters();
+        assert_eq!(grid.iter().collect::<String>(), "abcdef");
+        assert_eq!(grid.positions().nth(3), Some((1, 1)));
+        assert_eq!(grid.cells().last(), Some(((1, 2), &'f')));
+    }
+
+    #[test]
+    fn ray_walks_to_the_edge() {
+        let grid = Grid::from_fn(4, 4, |x, y| x + 10 * y);
+        let down: Vec<usize> = grid.ray(1, 0, (0, 1)).map(|(_, &cell)| cell).collect();
+        assert_eq!(down, [11, 21, 31]);
+        let diagonal: Vec<(usize, usize)> = grid.ray(3, 3, (-1, -1)).map(|(position, _)| position).collect();
+        assert_eq!(diagonal, [(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(grid.ray(0, 2, (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = letters();
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4(1, 1).count(), 3);
+        assert_eq!(grid.neighbours8(0, 1).collect::<Vec<_>>(), [(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn display_lines_up_wide_cells() {
+        let grid = Grid::from_vec(3, vec![1, 20, 3, 400, 5, 6]);
+        assert_eq!(grid.to_string(), "  1  20   3\n400   5   6");
+        assert_eq!(grid.map(|&n| n > 4).render(|&big| if big { '#' } else { '.' }), ".#.\n###");
+    }
+
+    #[test]
+    #[should_panic(expected = "5 cells don't make rows of 2")]
+    fn from_vec_needs_whole_rows() {
+        Grid::from_vec(2, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,324 @@
+// Pictures of puzzle state: a grid of colours, drawn from any grid through a
+// colour map and saved as PPM or PNG (see `aoc run --render`), or recorded
+// frame by frame as an animated GIF (see `aoc watch --record`).
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::animation::Frame;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    // The colour `t` of the way from `self` to `other`.
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t.clamp(0.0, 1.0)).round() as u8;
+        Rgb(channel(self.0, other.0), channel(self.1, other.1), channel(self.2, other.2))
+    }
+}
+
+pub type Image = Grid<Rgb>;
+
+// A solution that can draw a picture of each part, e.g. the trees visible
+// on day 8 or the screen on day 10.
+pub trait Picture: Solution {
+    fn picture(&self, part: u8) -> Image;
+}
+
+// Parses `input` and draws `part`.
+pub fn picture<P: Picture>(input: &str, part: u8) -> Result<Image, ParseError> {
+    Ok(P::parse(input)?.picture(part))
+}
+
+// Dark to bright through purple, red and orange, for 0 to 1.
+pub fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [Rgb(0, 0, 4), Rgb(87, 16, 110), Rgb(188, 55, 84), Rgb(249, 142, 9), Rgb(252, 255, 164)];
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) } * (STOPS.len() - 1) as f64;
+    let below = (t.floor() as usize).min(STOPS.len() - 2);
+    STOPS[below].mix(STOPS[below + 1], t - below as f64)
+}
+
+// `grid` as a heatmap, from its smallest value to its largest.
+pub fn heatmap<T: Copy + Into<f64>>(grid: &Grid<T>) -> Image {
+    let values = grid.iter().map(|&value| value.into());
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)));
+    let range = max - min;
+    grid.map(|&value| heat(if range > 0.0 { (value.into() - min) / range } else { 0.0 }))
+}
+
+// `on` where `grid` is true and `off` elsewhere.
+pub fn bitmap(grid: &Grid<bool>, on: Rgb, off: Rgb) -> Image {
+    grid.map(|&set| if set { on } else { off })
+}
+
+// The colour of a character in a drawing: '.' and ' ' are background, '#' is
+// foreground and anything else, like the H and T of a rope, stands out.
+pub fn palette(c: char) -> Rgb {
+    const MARKS: [Rgb; 6] =
+        [Rgb(230, 80, 70), Rgb(90, 180, 90), Rgb(80, 140, 230), Rgb(240, 190, 60), Rgb(190, 100, 220), Rgb(70, 200, 200)];
+    match c {
+        '.' | ' ' => Rgb(15, 15, 35),
+        '#' => Rgb(235, 235, 220),
+        _ => MARKS[c as usize % MARKS.len()],
+    }
+}
+
+// A frame of a simulation, coloured with `palette`.
+pub fn frame(frame: &Frame) -> Image {
+    frame.grid.map(|&c| palette(c))
+}
+
+// Every pixel of `image` as a `factor` by `factor` square.
+pub fn scale(image: &Image, factor: usize) -> Image {
+    assert!(factor > 0, "can't scale by 0");
+    Grid::from_fn(image.width() * factor, image.height() * factor, |x, y| image[(x / factor, y / factor)])
+}
+
+fn bytes(image: &Image) -> Vec<u8> {
+    image.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect()
+}
+
+// `image` as a binary PPM, which most viewers open and needs no compression.
+pub fn to_ppm(image: &Image) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    ppm.extend(bytes(image));
+    ppm
+}
+
+pub fn to_png(image: &Image) -> Vec<u8> {
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    // writing to memory only fails for a malformed image, which a grid can't be
+    let mut writer = encoder.write_header().expect("PNG header");
+    writer.write_image_data(&bytes(image)).expect("PNG data");
+    writer.finish().expect("PNG end");
+    png
+}
+
+// Writes `image` to `path` as a PNG or PPM, going by its extension.
+pub fn save(image: &Image, path: &Path) -> io::Result<()> {
+    let encoded = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("png") => to_png(image),
+        Some("ppm") => to_ppm(image),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "expected a .png or .ppm file")),
+    };
+    fs::write(path, encoded)
+}
+
+// How `write_gif` records a simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    pub fps: u32,
+    // each cell of a frame as a `scale` by `scale` square
+    pub scale: usize,
+    // keep every `every`th frame, and always the last
+    pub every: usize,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self { fps: 10, scale: 4, every: 1 }
+    }
+}
+
+// GIF delays are in hundredths of a second, and viewers slow down anything
+// shorter than two.
+pub const MAX_GIF_FPS: u32 = 50;
+
+// The frames `every` keeps: the first, every `every`th after it, and the last.
+pub fn sample<T>(frames: impl IntoIterator<Item = T>, every: usize) -> impl Iterator<Item = T> {
+    assert!(every > 0, "can't keep every 0th frame");
+    let mut frames = frames.into_iter().enumerate().peekable();
+    std::iter::from_fn(move || loop {
+        let (index, frame) = frames.next()?;
+        if index.is_multiple_of(every) || frames.peek().is_none() {
+            return Some(frame);
+        }
+    })
+}
+
+// Records `frames` as a looping GIF, returning how many frames it kept. Only
+// the part of each frame that changed is stored, so long simulations with
+// little going on in each step stay small.
+pub fn write_gif(frames: impl IntoIterator<Item = Frame>, recording: &Recording, out: impl Write) -> io::Result<usize> {
+    let delay = (100 / recording.fps.clamp(1, MAX_GIF_FPS)) as u16;
+    let mut images = sample(frames, recording.every).map(|shown| scale(&frame(&shown), recording.scale));
+    let Some(first) = images.next() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "there are no frames to record"));
+    };
+    let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "frames are too big for a GIF; try a smaller scale");
+    let width = u16::try_from(first.width()).map_err(|_| too_big())?;
+    let height = u16::try_from(first.height()).map_err(|_| too_big())?;
+
+    let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(gif_error)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+    encoder.write_frame(&gif_frame(&first, (0, 0), delay)).map_err(gif_error)?;
+    let mut previous = first;
+    let mut kept = 1;
+    for image in images {
+        if (image.width(), image.height()) != (previous.width(), previous.height()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "frames change size part way through"));
+        }
+        let (x, y, width, height) = changed(&previous, &image);
+        let patch = Grid::from_fn(width, height, |dx, dy| image[(x + dx, y + dy)]);
+        encoder.write_frame(&gif_frame(&patch, (x, y), delay)).map_err(gif_error)?;
+        previous = image;
+        kept += 1;
+    }
+    encoder.into_inner().map_err(gif_error)?;
+    Ok(kept)
+}
+
+pub fn save_gif(frames: impl IntoIterator<Item = Frame>, recording: &Recording, path: &Path) -> io::Result<usize> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let kept = write_gif(frames, recording, &mut out)?;
+    out.flush()?;
+    Ok(kept)
+}
+
+// The smallest rectangle, as its left, top, width and height, that holds
+// every pixel that differs between two images of the same size. Unchanged
+// images give their top left pixel, as a GIF frame can't be empty.
+fn changed(before: &Image, after: &Image) -> (usize, usize, usize, usize) {
+    let differ = after.cells().filter(|&(position, pixel)| before[position] != *pixel).map(|(position, _)| position);
+    let bounds = differ.fold(None, |bounds: Option<(usize, usize, usize, usize)>, (x, y)| {
+        Some(bounds.map_or((x, y, x, y), |(left, top, right, bottom)| (left.min(x), top.min(y), right.max(x), bottom.max(y))))
+    });
+    bounds.map_or((0, 0, 1, 1), |(left, top, right, bottom)| (left, top, right - left + 1, bottom - top + 1))
+}
+
+// `image` as a GIF frame placed at `left` and `top`, with a palette of its
+// own colours, or quantised to 256 of them if it has more.
+fn gif_frame(image: &Image, (left, top): (usize, usize), delay: u16) -> gif::Frame<'static> {
+    let (width, height) = (image.width() as u16, image.height() as u16);
+    let mut indices = HashMap::new();
+    let mut palette = vec![];
+    let mut pixels = Vec::with_capacity(image.width() * image.height());
+    for &Rgb(r, g, b) in image.iter() {
+        let index = *indices.entry((r, g, b)).or_insert_with(|| {
+            palette.extend([r, g, b]);
+            palette.len() / 3 - 1
+        });
+        pixels.push(index as u8);
+    }
+    let mut frame = if indices.len() <= 256 {
+        gif::Frame::from_palette_pixels(width, height, pixels, palette, None)
+    } else {
+        gif::Frame::from_rgb_speed(width, height, &bytes(image), 10)
+    };
+    frame.left = left as u16;
+    frame.top = top as u16;
+    frame.delay = delay;
+    frame
+}
+
+fn gif_error(err: gif::EncodingError) -> io::Error {
+    match err {
+        gif::EncodingError::Io(err) => err,
+        err => io::Error::other(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::iter;
+    use std::process;
+
+    #[test]
+    fn heatmap_spans_the_values() {
+        assert_eq!(heat(0.0), Rgb(0, 0, 4));
+        assert_eq!(heat(1.0), Rgb(252, 255, 164));
+        assert_eq!(heat(0.125), Rgb(44, 8, 57));
+        assert_eq!(heat(-3.0), heat(0.0));
+        let image = heatmap(&Grid::from_vec(3, vec![2, 4, 6]));
+        assert_eq!(image.iter().copied().collect::<Vec<_>>(), [heat(0.0), heat(0.5), heat(1.0)]);
+        assert_eq!(heatmap(&Grid::new(2, 2, 7))[(1, 1)], heat(0.0));
+    }
+
+    #[test]
+    fn scale_repeats_pixels() {
+        let image = bitmap(&Grid::from_vec(2, vec![true, false]), Rgb::WHITE, Rgb::BLACK);
+        let scaled = scale(&image, 3);
+        assert_eq!((scaled.width(), scaled.height()), (6, 3));
+        assert_eq!(scaled.render(|&pixel| if pixel == Rgb::WHITE { '#' } else { '.' }), "###...\n###...\n###...");
+    }
+
+    #[test]
+    fn ppm_has_a_header_and_raw_pixels() {
+        let image = Grid::from_vec(2, vec![Rgb(1, 2, 3), Rgb(4, 5, 6)]);
+        assert_eq!(to_ppm(&image), b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let image = Grid::from_fn(5, 3, |x, y| palette(if (x + y) % 2 == 0 { '#' } else { 'H' }));
+        let png = to_png(&image);
+        let mut reader = png::Decoder::new(io::Cursor::new(png)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (5, 3));
+        assert_eq!(pixels, bytes(&image));
+    }
+
+    #[test]
+    fn save_goes_by_the_extension() {
+        let path = |name: &str| env::temp_dir().join(format!("aoc_image_{}_{}", process::id(), name));
+        let image = Grid::new(1, 1, Rgb::WHITE);
+        save(&image, &path("a.ppm")).unwrap();
+        assert_eq!(fs::read(path("a.ppm")).unwrap(), to_ppm(&image));
+        save(&image, &path("a.png")).unwrap();
+        assert!(fs::read(path("a.png")).unwrap().starts_with(b"\x89PNG"));
+        assert!(save(&image, &path("a.jpg")).is_err());
+        fs::remove_file(path("a.ppm")).unwrap();
+        fs::remove_file(path("a.png")).unwrap();
+    }
+
+    #[test]
+    fn sample_keeps_every_nth_and_the_last() {
+        assert_eq!(sample(0..10, 4).collect::<Vec<_>>(), [0, 4, 8, 9]);
+        assert_eq!(sample(0..9, 4).collect::<Vec<_>>(), [0, 4, 8]);
+        assert_eq!(sample(0..3, 1).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(sample(0..0, 2).count(), 0);
+    }
+
+    #[test]
+    fn changed_bounds_the_differences() {
+        let before = Grid::new(4, 3, Rgb::BLACK);
+        let mut after = before.clone();
+        assert_eq!(changed(&before, &after), (0, 0, 1, 1));
+        after[(1, 2)] = Rgb::WHITE;
+        after[(3, 1)] = Rgb::WHITE;
+        assert_eq!(changed(&before, &after), (1, 1, 3, 2));
+    }
+
+    #[test]
+    fn gif_stores_what_changed() {
+        let dots = |x: usize| Frame::new(Grid::from_fn(4, 2, |i, _| if i == x { '#' } else { '.' }), "");
+        let recording = Recording { fps: 20, scale: 2, every: 2 };
+        let mut gif = vec![];
+        assert_eq!(write_gif((0..4).map(dots), &recording, &mut gif).unwrap(), 3);
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(io::Cursor::new(gif)).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (8, 4));
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.left, frame.top, frame.width, frame.height, frame.delay));
+        }
+        // frames 0, 2 and 3, the last two only where the dot moved
+        assert_eq!(frames, [(0, 0, 8, 4, 5), (0, 0, 6, 4, 5), (4, 0, 4, 4, 5)]);
+        assert!(write_gif(iter::empty(), &recording, &mut vec![]).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    // the day's own input, compiled in with `include_str!`
+    Embedded(&'static str),
+}
+
+impl Source {
+    // "-" reads stdin, anything else is a path, and no argument falls back
+    // to the embedded `default`.
+    pub fn from_arg(arg: Option<&str>, default: &'static str) -> Self {
+        match arg {
+            None => Source::Embedded(default),
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(path.into()),
+        }
+    }
+
+    // Picks the source from the first command-line argument.
+    pub fn from_args(default: &'static str) -> Self {
+        Self::from_arg(env::args().nth(1).as_deref(), default)
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::Path(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Source::Embedded(input) => Ok(input.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Embedded(_) => write!(f, "<embedded input>"),
+        }
+    }
+}
+
+// Iterates over every line of the file at `path`.
+// Panics with the offending path if the file cannot be opened or read,
+// which is what every day did with its own `unwrap()`s.
+pub fn lines<P: AsRef<Path>>(path: P) -> impl Iterator<Item = String> {
+    let path = path.as_ref().to_path_buf();
+    let file = File::open(&path)
+        .unwrap_or_else(|err| panic!("could not open {}: {}", path.display(), err));
+    BufReader::new(file)
+        .lines()
+        .map(move |line| {
+            line.unwrap_or_else(|err| panic!("could not read {}: {}", path.display(), err))
+        })
+}
+
+// Reads every line of the file at `path` into memory.
+pub fn read_lines<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(path)?).lines().collect()
+}
+
+// Reads the whole file at `path`.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    fs::read_to_string(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn write_fixture(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("aoc_common_{}_{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn lines_iterates_over_each_line() {
+        let path = write_fixture("lines", "a\nbb\n\nccc\n");
+        let actual: Vec<String> = lines(&path).collect();
+        assert_eq!(actual, vec!["a", "bb", "", "ccc"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn source_from_arg_picks_stdin_path_or_default() {
+        assert_eq!(Source::from_arg(None, "1\n"), Source::Embedded("1\n"));
+        assert_eq!(Source::from_arg(Some("-"), "1\n"), Source::Stdin);
+        assert_eq!(Source::from_arg(Some("other"), "1\n"), Source::Path("other".into()));
+    }
+
+    #[test]
+    fn source_reads_embedded_and_path_inputs() {
+        let path = write_fixture("source", "from a file\n");
+        assert_eq!(Source::Path(path.clone()).read().unwrap(), "from a file\n");
+        assert_eq!(Source::Embedded("compiled in").read().unwrap(), "compiled in");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn read_lines_returns_error_for_missing_file() {
+        assert!(read_lines("this/file/does/not/exist").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "could not open")]
+    fn lines_panics_with_path_for_missing_file() {
+        let _ = lines("this/file/does/not/exist").count();
+    }
+}
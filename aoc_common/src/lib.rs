@@ -0,0 +1,22 @@
+// Shared plumbing for every Advent of Code day in this repository.
+pub mod animation;
+pub mod bench;
+pub mod differential;
+pub mod error;
+pub mod examples;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod parse;
+pub mod random;
+pub mod search;
+pub mod solution;
+
+pub use error::{number, parse_lines, ParseError};
+pub use geometry::{Bounds, Direction, Point, Vec2};
+pub use grid::Grid;
+pub use input::{lines, read_lines, read_to_string, Source};
+pub use solution::{run, solve, Answer, Answers, Solution};
+// for days' `tracing` events, so they don't each need the dependency
+pub use tracing;
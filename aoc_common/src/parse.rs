@@ -0,0 +1,213 @@
+// Helpers for the shapes puzzle inputs come in. Each returns a `ParseError`
+// placed within the text it was given, so callers only need to place it on
+// the right line, as `parse_lines` does.
+use std::str::FromStr;
+
+use crate::error::{number, ParseError};
+
+// Every integer in `text`, skipping whatever is between them, e.g. the 1, 2
+// and 3 in "move 1 from 2 to 3". A '-' right before a number makes it
+// negative unless it follows a letter or digit, so "2-4" holds 2 and 4.
+pub fn numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let mut numbers = vec![];
+    let mut chars = text.char_indices().peekable();
+    let mut previous = None;
+    while let Some((start, c)) = chars.next() {
+        let signed = c == '-'
+            && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit())
+            && !previous.is_some_and(|previous: char| previous.is_alphanumeric());
+        if c.is_ascii_digit() || signed {
+            let mut end = start + 1;
+            while let Some((i, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                end = i + digit.len_utf8();
+            }
+            let token = &text[start..end];
+            numbers.push(number(token).map_err(|err| err.within(text, token))?);
+            previous = text[..end].chars().next_back();
+        } else {
+            previous = Some(c);
+        }
+    }
+    Ok(numbers)
+}
+
+// A run of non-blank lines, and the number of its first line in the input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Group<'a> {
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl Group<'_> {
+    // Parses the group with `parse`, moving any error from the group's lines
+    // to the input's.
+    pub fn parse<T>(&self, parse: impl FnOnce(&str) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        parse(self.text).map_err(|err| match err.line {
+            0 => err.at(self.line, self.text.lines().next().unwrap_or("")),
+            line => ParseError { line: self.line + line - 1, ..err },
+        })
+    }
+}
+
+// Splits `input` at blank lines, e.g. into one group per elf or into a
+// drawing and the moves that follow it.
+pub fn groups(input: &str) -> Vec<Group<'_>> {
+    let mut groups = vec![];
+    let mut current: Option<(usize, usize, usize)> = None;
+    let mut offset = 0;
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            groups.extend(current.take());
+        } else {
+            let (number, start, _) = current.unwrap_or((index + 1, offset, offset));
+            current = Some((number, start, offset + content.len()));
+        }
+        offset += line.len();
+    }
+    groups.extend(current);
+    groups.into_iter().map(|(line, start, end)| Group { line, text: &input[start..end] }).collect()
+}
+
+// Parses each blank-line separated group of `input` with `parse`.
+pub fn parse_groups<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    groups(input).iter().map(|group| group.parse(&parse)).collect()
+}
+
+// Splits `text` at the first `delimiter`, e.g. "2-4" at "-".
+pub fn pair<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter).ok_or_else(|| {
+        ParseError::in_line(format!("expected two parts separated by {:?}, found", delimiter), text, text)
+    })
+}
+
+// Splits `text` at the first `delimiter` and parses each side, e.g. "2-4,6-8"
+// into two ranges.
+pub fn parse_pair<A, B>(
+    text: &str,
+    delimiter: &str,
+    first: impl FnOnce(&str) -> Result<A, ParseError>,
+    second: impl FnOnce(&str) -> Result<B, ParseError>,
+) -> Result<(A, B), ParseError> {
+    let (left, right) = pair(text, delimiter)?;
+    let left = first(left).map_err(|err| err.inside(text, left))?;
+    let right = second(right).map_err(|err| err.inside(text, right))?;
+    Ok((left, right))
+}
+
+// Cuts `line` into columns `width` characters wide, the last of them
+// possibly narrower, e.g. "[Z] [M] [P]" into "[Z] ", "[M] " and "[P]".
+pub fn columns(line: &str, width: usize) -> Vec<&str> {
+    assert!(width > 0, "columns must be at least one character wide");
+    let mut starts: Vec<usize> = line.char_indices().map(|(i, _)| i).step_by(width).collect();
+    starts.push(line.len());
+    starts.windows(2).map(|bounds| &line[bounds[0]..bounds[1]]).collect()
+}
+
+// The whitespace-separated tokens of `line`, for matching with a slice
+// pattern like `["$", "cd", name]`.
+pub fn tokens(line: &str) -> Vec<&str> {
+    line.split_whitespace().collect()
+}
+
+// Matches `line` against `pattern`, keywords and `<placeholders>` separated
+// by spaces, returning the tokens in the placeholders. For example
+// "move 1 from 2 to 3" against "move <n> from <stack> to <stack>" gives
+// ["1", "2", "3"].
+pub fn fields<'a, const N: usize>(line: &'a str, pattern: &str) -> Result<[&'a str; N], ParseError> {
+    let words: Vec<&str> = pattern.split(' ').collect();
+    let placeholders = words.iter().filter(|word| is_placeholder(word)).count();
+    assert_eq!(placeholders, N, "{:?} has {} placeholders, not {}", pattern, placeholders, N);
+    let tokens = tokens(line);
+    let matches = tokens.len() == words.len()
+        && words.iter().zip(tokens.iter()).all(|(word, token)| is_placeholder(word) || word == token);
+    if !matches {
+        return Err(ParseError::in_line(format!("expected {}, found", pattern), line, line));
+    }
+    let fields: Vec<&str> =
+        words.iter().zip(tokens).filter(|(word, _)| is_placeholder(word)).map(|(_, token)| token).collect();
+    Ok(fields.try_into().unwrap())
+}
+
+fn is_placeholder(word: &str) -> bool {
+    word.len() > 2 && word.starts_with('<') && word.ends_with('>')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::parse_lines;
+
+    #[test]
+    fn numbers_finds_every_integer() {
+        assert_eq!(numbers::<u32>("move 1 from 22 to 3"), Ok(vec![1, 22, 3]));
+        assert_eq!(numbers::<i32>("x=-4, y=12..-3"), Ok(vec![-4, 12, -3]));
+        assert_eq!(numbers::<i32>("2-4,6-8"), Ok(vec![2, 4, 6, 8]));
+        assert_eq!(numbers::<u8>("no numbers here"), Ok(vec![]));
+        let err = numbers::<u8>("ok 12 then 300").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (12, "300"));
+        let err = numbers::<u8>("a -1").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (3, "-1"));
+    }
+
+    #[test]
+    fn groups_split_at_blank_lines() {
+        let input = "1\n2\n\n3\n\n\n4\n5\n";
+        let found: Vec<(usize, &str)> = groups(input).iter().map(|group| (group.line, group.text)).collect();
+        assert_eq!(found, [(1, "1\n2"), (4, "3"), (7, "4\n5")]);
+        assert!(groups("").is_empty());
+        assert_eq!(groups("\r\na\r\nb\r\n\r\n")[0], Group { line: 2, text: "a\r\nb" });
+    }
+
+    #[test]
+    fn parse_groups_places_errors_in_the_input() {
+        let sum = |group: &str| Ok(parse_lines(group, number::<u32>)?.into_iter().sum::<u32>());
+        assert_eq!(parse_groups("1\n2\n\n3\n", sum), Ok(vec![3, 3]));
+        let err = parse_groups("1\n\n2\n3x\n", sum).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (4, 1, "3x"));
+        let err = groups("1\n\nempty").last().unwrap().parse(|_| Err::<(), _>(ParseError::new("bad", "pt"))).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+
+    #[test]
+    fn pairs_split_and_locate_errors() {
+        assert_eq!(pair("2-4", "-"), Ok(("2", "4")));
+        assert_eq!(pair("a -> b -> c", " -> "), Ok(("a", "b -> c")));
+        let err = pair("2_4", "-").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (1, "2_4"));
+        let range = |text: &str| parse_pair(text, "-", number::<u32>, number::<u32>);
+        assert_eq!(parse_pair("2-4,6-8", ",", range, range), Ok(((2, 4), (6, 8))));
+        let err = parse_pair("2-4,6-8x", ",", range, range).unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (7, "8x"));
+        let err = parse_pair("2-4,68", ",", range, range).unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (5, "68"));
+    }
+
+    #[test]
+    fn columns_cut_fixed_widths() {
+        assert_eq!(columns("[Z] [M] [P]", 4), ["[Z] ", "[M] ", "[P]"]);
+        assert_eq!(columns("    [D]", 4), ["    ", "[D]"]);
+        assert_eq!(columns("ab↑d", 2), ["ab", "↑d"]);
+        assert!(columns("", 4).is_empty());
+    }
+
+    #[test]
+    fn fields_match_keywords() {
+        let pattern = "move <n> from <stack> to <stack>";
+        assert_eq!(fields(" move 1 from 2  to 3", pattern), Ok(["1", "2", "3"]));
+        let err = fields::<3>("move 1 form 2 to 3", pattern).unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (1, "expected move <n> from <stack> to <stack>, found"));
+        assert!(fields::<3>("move 1 from 2 to", pattern).is_err());
+        assert_eq!(fields("noop", "noop"), Ok([]));
+        assert_eq!(tokens("$ cd  a"), ["$", "cd", "a"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "has 1 placeholders, not 2")]
+    fn fields_checks_the_number_of_placeholders() {
+        let _ = fields::<2>("addx 1", "addx <n>");
+    }
+}
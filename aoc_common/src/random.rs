@@ -0,0 +1,119 @@
+// Random puzzle inputs, for testing solutions on more than the one real input
+// each day has (see `aoc gen`). The generator is our own, so the same seed
+// gives the same input whatever the dependencies' versions.
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+// SplitMix64: small and fast, and good enough for making up inputs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number from `0` up to but not including `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "no numbers below 0");
+        // multiplying keeps the bias of reducing 64 bits to `n` negligible
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // A number from `low` to `high` inclusive.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "{} is above {}", low, high);
+        low + self.below((high - low) as usize + 1) as i64
+    }
+
+    // True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// A solution that can make up valid inputs for itself.
+pub trait Generator: Solution {
+    // the size `generate` is given when none is asked for
+    const SIZE: usize;
+
+    // An input of about `size` things, like lines, moves or grid rows, as
+    // each day documents.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+// The input `seed` gives for `G`, `size` big or `G::SIZE` if that's `None`.
+pub fn generate<G: Generator>(seed: u64, size: Option<usize>) -> String {
+    G::generate(&mut Rng::new(seed), size.unwrap_or(G::SIZE))
+}
+
+// Checks that inputs from a range of seeds and sizes parse and can be
+// solved, for each day's tests.
+pub fn assert_generates<G: Generator>() {
+    for seed in 0..20 {
+        for size in [1, 2, 5, G::SIZE] {
+            let input = generate::<G>(seed, Some(size));
+            let solved: Result<G, ParseError> = G::parse(&input);
+            match solved {
+                Ok(solution) => {
+                    solution.part1();
+                    solution.part2();
+                }
+                Err(err) => panic!("seed {} size {} gave an input that doesn't parse: {}", seed, size, err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_repeat() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        // pinned, so a change to the generator can't go unnoticed
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn ranges_are_covered() {
+        let mut rng = Rng::new(1);
+        let mut seen = [0; 6];
+        for _ in 0..600 {
+            seen[(rng.between(-2, 3) + 2) as usize] += 1;
+        }
+        assert!(seen.iter().all(|&count| count > 60), "{:?}", seen);
+        assert!((0..100).all(|_| rng.below(1) == 0));
+        let heads = (0..1000).filter(|_| rng.chance(0.25)).count();
+        assert!((200..300).contains(&heads), "{}", heads);
+        let mut items: Vec<u32> = (0..10).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..10).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,346 @@
+// Shortest paths through graphs given by a neighbour function: from a node,
+// the nodes one step away (and, for weighted searches, what the step costs).
+// `Grid`s are searched through `grid_moves` and `grid_costs`.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::mem;
+use std::ops::Add;
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+
+// A path found by a search, from the start to the node that met the goal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    // The node that met the goal.
+    pub fn end(&self) -> &N {
+        self.nodes.last().unwrap()
+    }
+}
+
+// The nodes a search has seen, so each is only searched from once.
+pub trait Visited<N> {
+    // Marks `node` as visited, returning whether it wasn't already.
+    fn visit(&mut self, node: &N) -> bool;
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> for HashSet<N> {
+    fn visit(&mut self, node: &N) -> bool {
+        !self.contains(node) && self.insert(node.clone())
+    }
+}
+
+// For searching a grid without hashing positions: one flag per cell.
+impl Visited<(usize, usize)> for Grid<bool> {
+    fn visit(&mut self, &(x, y): &(usize, usize)) -> bool {
+        !mem::replace(&mut self[(x, y)], true)
+    }
+}
+
+// Follows the parents back from `index` in the list of found nodes.
+fn reconstruct<N: Clone, C>(found: &[(N, Option<usize>)], mut index: usize, cost: C) -> Path<N, C> {
+    let mut nodes = vec![found[index].0.clone()];
+    while let Some(parent) = found[index].1 {
+        nodes.push(found[parent].0.clone());
+        index = parent;
+    }
+    nodes.reverse();
+    Path { nodes, cost }
+}
+
+// Breadth-first search from `start` to the nearest node meeting `goal`,
+// counting each step as 1.
+pub fn bfs<N, I>(start: N, neighbours: impl FnMut(&N) -> I, goal: impl FnMut(&N) -> bool) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_with(start, &mut HashSet::new(), neighbours, goal)
+}
+
+// As `bfs`, keeping track of the nodes seen in `visited`.
+pub fn bfs_with<N, I>(
+    start: N,
+    visited: &mut impl Visited<N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+{
+    visited.visit(&start);
+    // every node found, with the index of the one it was found from, in the
+    // order they are searched from
+    let mut found = vec![(start, None)];
+    let mut steps = vec![0];
+    let mut next = 0;
+    while next < found.len() {
+        let index = next;
+        next += 1;
+        if goal(&found[index].0) {
+            return Some(reconstruct(&found, index, steps[index]));
+        }
+        for node in neighbours(&found[index].0) {
+            if visited.visit(&node) {
+                found.push((node, Some(index)));
+                steps.push(steps[index] + 1);
+            }
+        }
+    }
+    None
+}
+
+// Every node reachable from `start`, with the fewest steps it takes to
+// reach it, nearest first.
+pub fn reachable<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<(N, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut found = vec![(start, 0)];
+    let mut next = 0;
+    while next < found.len() {
+        let (node, steps) = found[next].clone();
+        next += 1;
+        for node in neighbours(&node) {
+            if visited.visit(&node) {
+                found.push((node, steps + 1));
+            }
+        }
+    }
+    found
+}
+
+// Dijkstra's algorithm: the cheapest path from `start` to a node meeting
+// `goal`, where `neighbours` gives each next node with the cost of the step.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+// A*: as `dijkstra`, searching first where `heuristic` guesses the rest of
+// the path is cheapest. The path is the cheapest as long as the heuristic
+// never guesses more than the real cost, as with `manhattan` for orthogonal
+// steps costing at least 1.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut best = HashMap::from([(start.clone(), zero)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut found = vec![(start, None)];
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = &found[index].0;
+        // a cheaper way here was found after this one was queued
+        if best.get(node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        if goal(node) {
+            return Some(reconstruct(&found, index, cost));
+        }
+        for (next, step) in neighbours(node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&best| next_cost < best) {
+                best.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost + heuristic(&next), next_cost, found.len())));
+                found.push((next, Some(index)));
+            }
+        }
+    }
+    None
+}
+
+// A heuristic for orthogonal steps: the Manhattan distance to `goal`.
+pub fn manhattan<N: Copy + Into<Point>>(goal: N) -> impl Fn(&N) -> usize {
+    let goal = goal.into();
+    move |&node| node.into().manhattan(goal)
+}
+
+// A heuristic for steps that may be diagonal: the Chebyshev distance to
+// `goal`.
+pub fn chebyshev<N: Copy + Into<Point>>(goal: N) -> impl Fn(&N) -> usize {
+    let goal = goal.into();
+    move |&node| node.into().chebyshev(goal)
+}
+
+// A `Grid` position, as `(x, y)`.
+type Position = (usize, usize);
+
+// The neighbour function for moving between orthogonally adjacent cells of
+// `grid`, where `allowed` says whether a step from one cell to the next is.
+pub fn grid_moves<'a, T>(
+    grid: &'a Grid<T>,
+    allowed: impl Fn(&T, &T) -> bool + 'a,
+) -> impl FnMut(&Position) -> Vec<Position> + 'a {
+    move |&(x, y)| grid.neighbours4(x, y).filter(|&next| allowed(&grid[(x, y)], &grid[next])).collect()
+}
+
+// The weighted neighbour function for moving between orthogonally adjacent
+// cells of `grid`, where `cost` gives what a step from one cell to the next
+// costs, or `None` if it isn't allowed.
+pub fn grid_costs<'a, T, C>(
+    grid: &'a Grid<T>,
+    cost: impl Fn(&T, &T) -> Option<C> + 'a,
+) -> impl FnMut(&Position) -> Vec<(Position, C)> + 'a {
+    move |&(x, y)| {
+        grid.neighbours4(x, y)
+            .filter_map(|next| Some((next, cost(&grid[(x, y)], &grid[next])?)))
+            .collect()
+    }
+}
+
+// Breadth-first search of `grid` from `start`, stepping between
+// orthogonally adjacent cells that `allowed` allows.
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    start: Position,
+    allowed: impl Fn(&T, &T) -> bool,
+    goal: impl FnMut(&Position) -> bool,
+) -> Option<Path<Position, usize>> {
+    let mut visited = Grid::new(grid.width(), grid.height(), false);
+    bfs_with(start, &mut visited, grid_moves(grid, allowed), goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        let map = "\
+S.#.....
+.##.###.
+....#...
+.##.#.#.
+..#...#E
+";
+        Grid::parse(map, "a wall or floor", Some).unwrap()
+    }
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    #[test]
+    fn bfs_finds_the_fewest_steps() {
+        // from 0, add one or double, until 10
+        let path = bfs(0u32, |&n| [n + 1, n * 2], |&n| n == 10).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.nodes, [0, 1, 2, 4, 5, 10]);
+        assert_eq!(*path.end(), 10);
+        assert_eq!(bfs(0u32, |&n| (n < 5).then_some(n + 1), |&n| n == 10), None);
+    }
+
+    #[test]
+    fn grid_bfs_walks_around_walls() {
+        let grid = maze();
+        let path = grid_bfs(&grid, (0, 0), open, |&(x, y)| grid[(x, y)] == 'E').unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!((path.nodes[0], *path.end()), ((0, 0), (7, 4)));
+        // every step is to an orthogonal neighbour that isn't a wall
+        for step in path.nodes.windows(2) {
+            assert_eq!(Point::from(step[0]).manhattan(Point::from(step[1])), 1);
+            assert_ne!(grid[step[1]], '#');
+        }
+        assert_eq!(grid_bfs(&grid, (0, 0), |_, _| false, |&(x, y)| grid[(x, y)] == 'E'), None);
+    }
+
+    #[test]
+    fn visited_sets_see_each_node_once() {
+        let mut set = HashSet::new();
+        assert!(set.visit(&"a"));
+        assert!(!set.visit(&"a"));
+        let mut grid = Grid::new(2, 2, false);
+        assert!(grid.visit(&(1, 0)));
+        assert!(!grid.visit(&(1, 0)));
+        assert_eq!(grid.iter().filter(|&&seen| seen).count(), 1);
+    }
+
+    #[test]
+    fn reachable_counts_steps_to_every_node() {
+        let grid = maze();
+        let found = reachable((0, 0), grid_moves(&grid, open));
+        assert_eq!(found.len(), grid.iter().filter(|&&c| c != '#').count());
+        assert_eq!(found[0], ((0, 0), 0));
+        assert!(found.contains(&((7, 4), 15)));
+        assert!(found.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    // the risk levels from the example of 2021 day 15, where entering a cell
+    // costs its digit
+    fn risks() -> Grid<u32> {
+        let map = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+        Grid::parse(map, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let grid = risks();
+        let end = (grid.width() - 1, grid.height() - 1);
+        let path = dijkstra((0, 0), grid_costs(&grid, |_, &to| Some(to)), |&node| node == end).unwrap();
+        assert_eq!(path.cost, 40);
+        let cost: u32 = path.nodes[1..].iter().map(|&node| grid[node]).sum();
+        assert_eq!(cost, 40);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let grid = risks();
+        let end = (grid.width() - 1, grid.height() - 1);
+        let costs = grid_costs(&grid, |_, &to| Some(to as usize));
+        let path = astar((0, 0), costs, manhattan(end), |&node| node == end).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!(path.nodes[0], (0, 0));
+    }
+
+    #[test]
+    fn astar_searches_an_unbounded_plane() {
+        // a wall from (5, -10) to (5, 10) in the way
+        let wall = |point: &Point| point.x == 5 && (-10..=10).contains(&point.y);
+        let goal = Point::new(10, 0);
+        let steps = |point: &Point| {
+            let point = *point;
+            point.neighbours8().filter(move |next| !wall(next)).map(|next| (next, 1))
+        };
+        let path = astar(Point::ORIGIN, steps, chebyshev(goal), |&point| point == goal).unwrap();
+        // diagonally past the end of the wall and back
+        assert_eq!(path.cost, 22);
+        assert!(path.nodes.iter().any(|point| point.x == 5 && point.y.abs() == 11));
+        assert_eq!(chebyshev(goal)(&Point::ORIGIN), 10);
+    }
+}
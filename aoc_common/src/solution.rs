@@ -0,0 +1,110 @@
+use std::fmt::Display;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::error::ParseError;
+use crate::input::Source;
+
+// A day's puzzle: parse the input once, then answer each part from it.
+pub trait Solution: Sized {
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+    fn part1(&self) -> Self::Part1;
+    fn part2(&self) -> Self::Part2;
+}
+
+// A single part's answer, rendered, with how long it took to compute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+impl Answer {
+    fn time<T: Display>(f: impl FnOnce() -> T) -> Self {
+        let start = Instant::now();
+        let value = f();
+        let elapsed = start.elapsed();
+        Self { value: value.to_string(), elapsed }
+    }
+}
+
+// Both answers of a day, plus the time spent parsing its input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answers {
+    pub parse: Duration,
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+// Parses `input` and answers both parts, timing each step.
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, ParseError> {
+    let start = Instant::now();
+    let solution = S::parse(input)?;
+    let parse = start.elapsed();
+    Ok(Answers {
+        parse,
+        part1: Answer::time(|| solution.part1()),
+        part2: Answer::time(|| solution.part2()),
+    })
+}
+
+// Parses the input from `source` and prints both answers.
+pub fn run<S: Solution>(source: &Source) {
+    let input = source
+        .read()
+        .unwrap_or_else(|err| panic!("could not read {}: {}", source, err));
+    let solution = S::parse(&input).unwrap_or_else(|err| {
+        eprint!("{}", err.diagnostic(&input, &source.to_string()));
+        process::exit(1);
+    });
+    println!("Part 1: {}", solution.part1());
+    println!("Part 2: {}", solution.part2());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{number, parse_lines};
+
+    struct Sum(Vec<u32>);
+
+    impl Solution for Sum {
+        type Part1 = u32;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self, ParseError> {
+            Ok(Sum(parse_lines(input, number)?))
+        }
+
+        fn part1(&self) -> u32 {
+            self.0.iter().sum()
+        }
+
+        fn part2(&self) -> String {
+            format!("{:?}", self.0)
+        }
+    }
+
+    #[test]
+    fn solution_answers_are_displayable() {
+        let solution = Sum::parse("1\n2\n3\n").unwrap();
+        assert_eq!(solution.part1().to_string(), "6");
+        assert_eq!(solution.part2().to_string(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn solve_renders_both_answers() {
+        let answers = solve::<Sum>("4\n5\n").unwrap();
+        assert_eq!(answers.part1.value, "9");
+        assert_eq!(answers.part2.value, "[4, 5]");
+    }
+
+    #[test]
+    fn solve_returns_parse_errors() {
+        let err = solve::<Sum>("4\nfive\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// parsing may reject the input, but neither it nor solving what it accepts
+// may panic
+fuzz_target!(|input: &str| {
+    let _ = aoc_common::solve::<aoc_2022_day_1::Day1>(input);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// parsing may reject the input, but neither it nor solving what it accepts
+// may panic
+fuzz_target!(|input: &str| {
+    // the tail can visit a new position every step, so huge distances run
+    // out of memory rather than into bugs
+    if input.split_whitespace().any(|token| token.len() > 4) {
+        return;
+    }
+    let _ = aoc_common::solve::<aoc_2022_day_9::Day9>(input);
+});
@@ -0,0 +1,41 @@
+use aoc_common::{ParseError, Solution};
+
+// the puzzle input, used when no other input is given
+pub const INPUT: &str = include_str!("../input");
+
+pub struct Day{{day}} {
+    lines: Vec<String>,
+}
+
+impl Solution for Day{{day}} {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self { lines: input.lines().map(String::from).collect() })
+    }
+
+    fn part1(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn part2(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_examples() {
+        aoc_common::examples::assert_examples::<Day{{day}}>(include_str!("../README.md"));
+    }
+
+    #[test]
+    fn parse_keeps_every_line() {
+        let solution = Day{{day}}::parse("a\nb\n").unwrap();
+        assert_eq!(solution.lines.len(), 2);
+    }
+}
@@ -1,28 +1,7 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-const FILENAME: &str = "input";
+use aoc_{{year}}_day_{{day}}::{Day{{day}}, INPUT};
+use aoc_common::Source;
 
+// usage: cargo run [<path> | -]
 fn main() {
-    let file = File::open(FILENAME).unwrap();
-    let reader = BufReader::new(file);
-
-    let mut sum = 0;
-    for (_index, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-    }
-
-    println!("The sum is {}", sum);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test() {
-        assert_eq!(1, 1);
-    }
+    aoc_common::run::<Day{{day}}>(&Source::from_args(INPUT));
 }